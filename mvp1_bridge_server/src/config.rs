@@ -21,6 +21,56 @@ pub struct LimitsConfig {
     pub max_ws_message_bytes: usize,
     pub hello_timeout_ms: u64,
     pub send_timeout_ms: u64,
+    /// Envelopes smaller than this are sent uncompressed even when
+    /// `CapCompressionV1` was negotiated, since zstd's framing overhead can
+    /// make tiny frames (e.g. heartbeats) larger, not smaller.
+    #[serde(default = "default_compression_min_bytes")]
+    pub compression_min_bytes: usize,
+    /// How often the server sends a WebSocket ping on an otherwise idle
+    /// session.
+    #[serde(default = "default_ping_interval_ms")]
+    pub ping_interval_ms: u64,
+    /// A session with no received frame (ping, pong, or envelope) for this
+    /// long is considered dead and closed.
+    #[serde(default = "default_ping_timeout_ms")]
+    pub ping_timeout_ms: u64,
+    /// How often an unacked reliable frame (action dispatch/ack/result) is
+    /// resent while its `seq` hasn't been covered by the peer's `ack`.
+    #[serde(default = "default_reliable_retry_interval_ms")]
+    pub reliable_retry_interval_ms: u64,
+    /// Reliable frames are resent at most this many times before the send
+    /// is given up on and reported as a failure.
+    #[serde(default = "default_reliable_max_retries")]
+    pub reliable_max_retries: u32,
+    /// High-water mark, in bytes, for a session's outbound coalescable
+    /// (telemetry) queue. Once exceeded, the oldest queued frames are
+    /// dropped to make room rather than letting the queue grow unbounded.
+    #[serde(default = "default_outbound_high_water_bytes")]
+    pub outbound_high_water_bytes: usize,
+}
+
+fn default_compression_min_bytes() -> usize {
+    256
+}
+
+fn default_ping_interval_ms() -> u64 {
+    15_000
+}
+
+fn default_ping_timeout_ms() -> u64 {
+    45_000
+}
+
+fn default_reliable_retry_interval_ms() -> u64 {
+    2_000
+}
+
+fn default_reliable_max_retries() -> u32 {
+    5
+}
+
+fn default_outbound_high_water_bytes() -> usize {
+    2_000_000
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -28,6 +78,46 @@ pub struct RelayConfig {
     pub allow_orchestrator_subscribe: bool,
     pub max_orchestrator_subscribers: usize,
     pub min_relay_interval_ms: u64,
+    pub action_queue_size: usize,
+    pub action_send_timeout_ms: u64,
+    pub primary_game_agent_id: String,
+    /// Outbound frames retained per session_id so a reconnecting peer can
+    /// resume instead of re-subscribing from scratch.
+    #[serde(default = "default_replay_buffer_frames")]
+    pub replay_buffer_frames: usize,
+    /// Caps memory held by disconnected-but-resumable sessions; oldest
+    /// buffer is dropped once this many sessions are retained at once.
+    #[serde(default = "default_max_replay_sessions")]
+    pub max_replay_sessions: usize,
+    /// How long `RelayHub::enqueue_action` parks an action for a
+    /// `target_agent_id` that isn't attached yet, waiting for that agent to
+    /// connect, before failing fast. `0` disables parking entirely (the
+    /// original fail-fast behavior).
+    #[serde(default)]
+    pub agent_attach_wait_ms: u64,
+    /// How long an unacked `ActionRequest` is held (and replayed if the same
+    /// agent reattaches) after its agent disconnects, instead of being
+    /// failed immediately. `0` disables the grace window (the original
+    /// behavior).
+    #[serde(default)]
+    pub reconnect_grace_ms: u64,
+    /// Frames kept in `RelayHub`'s sequenced telemetry history ring, used
+    /// to serve `subscribe_telemetry_from` and to size its backing
+    /// broadcast channel. See `TelemetrySubscription`.
+    #[serde(default = "default_telemetry_history_len")]
+    pub telemetry_history_len: usize,
+}
+
+fn default_replay_buffer_frames() -> usize {
+    256
+}
+
+fn default_max_replay_sessions() -> usize {
+    64
+}
+
+fn default_telemetry_history_len() -> usize {
+    256
 }
 
 impl BridgeConfig {
@@ -51,6 +141,21 @@ impl BridgeConfig {
         if self.limits.send_timeout_ms == 0 {
             bail!("send_timeout_ms must be > 0");
         }
+        if self.limits.ping_interval_ms == 0 {
+            bail!("ping_interval_ms must be > 0");
+        }
+        if self.limits.ping_timeout_ms <= self.limits.ping_interval_ms {
+            bail!("ping_timeout_ms must be greater than ping_interval_ms");
+        }
+        if self.limits.reliable_retry_interval_ms == 0 {
+            bail!("reliable_retry_interval_ms must be > 0");
+        }
+        if self.limits.reliable_max_retries == 0 {
+            bail!("reliable_max_retries must be > 0");
+        }
+        if self.limits.outbound_high_water_bytes < 1024 {
+            bail!("outbound_high_water_bytes too small");
+        }
         if self.relay.max_orchestrator_subscribers == 0 {
             bail!("max_orchestrator_subscribers must be > 0");
         }
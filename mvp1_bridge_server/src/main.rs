@@ -44,20 +44,14 @@ async fn main() -> Result<()> {
                         }
                     };
 
-                    if let Err(e) = ws::run_ws_session(
-                        tls,
-                        limits.max_ws_message_bytes,
-                        limits.hello_timeout_ms,
-                        relay_hub,
-                    )
-                    .await
-                    {
+                    if let Err(e) = ws::run_ws_session(tls, &limits, relay_hub).await {
                         warn!(peer = %addr, error = %e, "ws session error");
                     }
                 });
             }
             _ = tokio::signal::ctrl_c() => {
                 info!("shutdown");
+                relay_hub.trigger_shutdown();
                 break;
             }
         }
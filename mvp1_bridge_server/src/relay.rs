@@ -1,12 +1,56 @@
 use anyhow::Result;
-use std::collections::HashMap;
+use dashmap::DashMap;
+use std::collections::{HashMap, VecDeque};
 use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
-use tokio::sync::{mpsc, watch, Mutex};
+use tokio::sync::{broadcast, mpsc, watch, Mutex, Notify};
 
 use crate::config::RelayConfig;
 use crate::pb::bridge_v1 as pb;
 
+/// A bounded ring of recently sent `Envelope`s for one `session_id`, kept
+/// around after disconnect so a reconnecting peer can resume instead of
+/// re-subscribing from scratch (see `RelayHub::take_replay_frames`).
+struct ReplayBuffer {
+    frames: VecDeque<pb::Envelope>,
+    max_frames: usize,
+    last_seq: u64,
+}
+
+impl ReplayBuffer {
+    fn new(max_frames: usize) -> Self {
+        Self {
+            frames: VecDeque::new(),
+            max_frames,
+            last_seq: 0,
+        }
+    }
+
+    fn push(&mut self, env: pb::Envelope) {
+        self.last_seq = self.last_seq.max(env.seq);
+        if self.frames.len() >= self.max_frames {
+            self.frames.pop_front();
+        }
+        self.frames.push_back(env);
+    }
+
+    fn evict_acked(&mut self, ack: u64) {
+        while matches!(self.frames.front(), Some(f) if f.seq <= ack) {
+            self.frames.pop_front();
+        }
+    }
+
+    /// Frames with `seq > ack`, or `None` if some of what the peer is
+    /// asking to resume from has already been evicted from the ring.
+    fn frames_after(&self, ack: u64) -> Option<Vec<pb::Envelope>> {
+        match self.frames.front() {
+            Some(front) if front.seq > ack + 1 => None,
+            None if ack < self.last_seq => None,
+            _ => Some(self.frames.iter().filter(|f| f.seq > ack).cloned().collect()),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum OrchestratorAcquireError {
     NotAllowed,
@@ -19,30 +63,304 @@ pub enum ActionRelayFrame {
     Result(pb::ActionResult),
 }
 
+/// A `TelemetryFrame` stamped with the hub-assigned, monotonically
+/// increasing `seq` it was published under. Distinct from the frame's own
+/// `state_version` (game-state concept), `seq` is purely about relay
+/// ordering/continuity for `subscribe_telemetry_from`.
+#[derive(Clone)]
+pub struct TelemetryEvent {
+    pub seq: u64,
+    pub frame: pb::TelemetryFrame,
+}
+
+/// An item yielded by `TelemetrySubscription::recv`.
+pub enum TelemetryStreamItem {
+    Frame(TelemetryEvent),
+    /// The subscription fell behind the broadcast channel's buffer before
+    /// it could be drained. `resync_seq` is the oldest `seq` still covered
+    /// by the history ring, so the caller can re-subscribe from there
+    /// without another gap.
+    Lagged { resync_seq: u64 },
+}
+
+/// A sequenced telemetry stream returned by `subscribe_telemetry_from`:
+/// first drains whatever the history ring still has at or after the
+/// requested `seq`, then continues live off the broadcast channel.
+pub struct TelemetrySubscription {
+    buffered: VecDeque<TelemetryEvent>,
+    live: broadcast::Receiver<TelemetryEvent>,
+    last_seq: u64,
+    history: Arc<Mutex<VecDeque<TelemetryEvent>>>,
+}
+
+impl TelemetrySubscription {
+    /// Waits for the next frame, or `None` once the hub itself has shut
+    /// down (the broadcast channel closed).
+    pub async fn recv(&mut self) -> Option<TelemetryStreamItem> {
+        loop {
+            if let Some(event) = self.buffered.pop_front() {
+                self.last_seq = event.seq;
+                return Some(TelemetryStreamItem::Frame(event));
+            }
+            match self.live.recv().await {
+                Ok(event) => {
+                    // Already delivered from the buffered drain above; the
+                    // live receiver was subscribed before that snapshot was
+                    // taken, so their ranges can overlap.
+                    if event.seq <= self.last_seq {
+                        continue;
+                    }
+                    self.last_seq = event.seq;
+                    return Some(TelemetryStreamItem::Frame(event));
+                }
+                Err(broadcast::error::RecvError::Lagged(_)) => {
+                    let resync_seq = {
+                        let history = self.history.lock().await;
+                        history.front().map(|e| e.seq).unwrap_or(self.last_seq + 1)
+                    };
+                    return Some(TelemetryStreamItem::Lagged { resync_seq });
+                }
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    }
+}
+
+/// One attached game agent's action inbox.
+struct AgentSlot {
+    sender: mpsc::Sender<pb::ActionRequest>,
+}
+
+/// A single interest assertion an `OrchestratorSlot` can declare at
+/// acquire time. `publish_telemetry`/`route_action_result` only forward
+/// events to slots with a matching assertion, instead of every orchestrator
+/// getting the full firehose.
+#[derive(Clone, Debug)]
+pub enum Interest {
+    /// Every telemetry frame, unfiltered — the old firehose behavior.
+    AnyTelemetry,
+    /// Telemetry frames reporting `hp` at or below the threshold (e.g. a
+    /// low-health watch).
+    TelemetryHpAtMost(i32),
+    /// `ActionResult`s for actions that were routed to this
+    /// `target_agent_id`, keyed off the same `agent_id` `enqueue_action`
+    /// already tracks per pending action.
+    ActionResultForAgent(String),
+}
+
+/// Channels handed back alongside an `OrchestratorSlot` for whichever
+/// `Interest` variants were declared; a slot that declared no telemetry (or
+/// no action-result) interests simply never receives on the matching
+/// channel.
+pub struct OrchestratorInterestHandles {
+    pub telemetry_rx: mpsc::Receiver<TelemetryEvent>,
+    pub action_result_rx: mpsc::Receiver<pb::ActionResult>,
+}
+
+/// An in-flight `ActionRequest` awaiting its `ActionAck`/`ActionResult`,
+/// remembering which agent it was routed to so a disconnect only fails the
+/// pending actions that actually belonged to that agent, and a copy of the
+/// request itself so it can be replayed if that agent reattaches within
+/// `reconnect_grace_ms` (see `held_actions`).
+struct PendingAction {
+    agent_id: String,
+    request: pb::ActionRequest,
+    reply_tx: mpsc::Sender<ActionRelayFrame>,
+    /// Set once an `ActionAck{accepted: true}` has been routed for this
+    /// request. An acked action is already running on the agent side, so a
+    /// disconnect can't safely replay it — it fails immediately instead of
+    /// entering the reconnect grace window.
+    acked: bool,
+}
+
+/// A `PendingAction` pulled off `pending_actions` on disconnect, parked here
+/// until either its owning agent reattaches (and it's resent) or
+/// `deadline_ms` passes (and it's failed like it always used to be).
+struct HeldAction {
+    agent_id: String,
+    request: pb::ActionRequest,
+    reply_tx: mpsc::Sender<ActionRelayFrame>,
+    deadline_ms: u64,
+}
+
 pub struct RelayHub {
     relay_cfg: RelayConfig,
-    telemetry_tx: watch::Sender<Option<pb::TelemetryFrame>>,
+    telemetry_broadcast: broadcast::Sender<TelemetryEvent>,
+    telemetry_history: Arc<Mutex<VecDeque<TelemetryEvent>>>,
+    telemetry_next_seq: AtomicU64,
+    shutdown_tx: watch::Sender<bool>,
     orchestrator_count: AtomicUsize,
     last_relay_mono_ms: AtomicU64,
-    primary_game_sender: Mutex<Option<mpsc::Sender<pb::ActionRequest>>>,
-    pending_actions: Mutex<HashMap<String, mpsc::Sender<ActionRelayFrame>>>,
+    agents: DashMap<String, AgentSlot>,
+    /// Notified whenever an agent attaches, so `enqueue_action` can park a
+    /// request for a not-yet-attached `target_agent_id` instead of failing
+    /// it outright. Entries are created on demand and left in place (a
+    /// `Notify` is cheap and harmless to keep around for an agent that may
+    /// reattach later).
+    agent_attach_notify: DashMap<String, Arc<Notify>>,
+    pending_actions: DashMap<String, PendingAction>,
+    /// Unacked actions held across a disconnect within `reconnect_grace_ms`,
+    /// keyed by `request_id`. Swept by the background task spawned in
+    /// `new()` once their `deadline_ms` passes.
+    held_actions: DashMap<String, HeldAction>,
+    replay_buffers: Mutex<HashMap<String, ReplayBuffer>>,
+    replay_session_order: Mutex<VecDeque<String>>,
+
+    next_slot_id: AtomicU64,
+    /// Source of truth for what each orchestrator slot declared, so `Drop`
+    /// knows exactly which index entries to retract without having to
+    /// reverse-engineer it from the index shapes themselves.
+    slot_interests: DashMap<u64, Vec<Interest>>,
+    /// Index for `Interest::AnyTelemetry`.
+    telemetry_any: DashMap<u64, mpsc::Sender<TelemetryEvent>>,
+    /// Index for `Interest::TelemetryHpAtMost`; small (one entry per
+    /// subscribed slot) so a linear scan per publish is cheap, but isolated
+    /// from `action_result_by_agent` so publishing telemetry never touches
+    /// action-result subscribers or vice versa.
+    telemetry_hp_at_most: DashMap<u64, (i32, mpsc::Sender<TelemetryEvent>)>,
+    /// Index for `Interest::ActionResultForAgent`, keyed by the
+    /// `target_agent_id` itself so routing a result only looks at slots
+    /// actually interested in that agent.
+    action_result_by_agent: DashMap<String, DashMap<u64, mpsc::Sender<pb::ActionResult>>>,
 }
 
+/// How often the held-action sweeper wakes up to expire anything past its
+/// deadline. Independent of `reconnect_grace_ms` itself; just a polling
+/// granularity.
+const RECONNECT_SWEEP_INTERVAL_MS: u64 = 250;
+
 impl RelayHub {
     pub fn new(relay_cfg: RelayConfig) -> Arc<Self> {
-        let (telemetry_tx, _rx) = watch::channel(None);
-        Arc::new(Self {
+        let (telemetry_broadcast, _rx) = broadcast::channel(relay_cfg.telemetry_history_len.max(1));
+        let (shutdown_tx, _rx) = watch::channel(false);
+        let hub = Arc::new(Self {
             relay_cfg,
-            telemetry_tx,
+            telemetry_broadcast,
+            telemetry_history: Arc::new(Mutex::new(VecDeque::new())),
+            telemetry_next_seq: AtomicU64::new(1),
+            shutdown_tx,
             orchestrator_count: AtomicUsize::new(0),
             last_relay_mono_ms: AtomicU64::new(0),
-            primary_game_sender: Mutex::new(None),
-            pending_actions: Mutex::new(HashMap::new()),
-        })
+            agents: DashMap::new(),
+            agent_attach_notify: DashMap::new(),
+            pending_actions: DashMap::new(),
+            held_actions: DashMap::new(),
+            replay_buffers: Mutex::new(HashMap::new()),
+            replay_session_order: Mutex::new(VecDeque::new()),
+            next_slot_id: AtomicU64::new(1),
+            slot_interests: DashMap::new(),
+            telemetry_any: DashMap::new(),
+            telemetry_hp_at_most: DashMap::new(),
+            action_result_by_agent: DashMap::new(),
+        });
+        if hub.relay_cfg.reconnect_grace_ms > 0 {
+            let sweep_hub = Arc::clone(&hub);
+            tokio::spawn(async move { sweep_hub.run_held_action_sweeper().await });
+        }
+        hub
+    }
+
+    /// Periodically fails any `held_actions` entry whose grace-window
+    /// deadline has passed, same as `fail_agent_pending` would have done
+    /// immediately before `reconnect_grace_ms` existed.
+    async fn run_held_action_sweeper(self: Arc<Self>) {
+        let mut ticker = tokio::time::interval(std::time::Duration::from_millis(RECONNECT_SWEEP_INTERVAL_MS));
+        loop {
+            ticker.tick().await;
+            let now = mono_ms();
+            let expired: Vec<String> = self
+                .held_actions
+                .iter()
+                .filter(|entry| entry.value().deadline_ms <= now)
+                .map(|entry| entry.key().clone())
+                .collect();
+            for request_id in expired {
+                if let Some((_, held)) = self.held_actions.remove(&request_id) {
+                    Self::fail_one(request_id, held.reply_tx, "reconnect grace window expired").await;
+                }
+            }
+        }
+    }
+
+    /// Signals every live session to send a `Disconnect{ServerShutdown}` and
+    /// close. Idempotent; sessions that have already subscribed see the
+    /// change on their next `tokio::select!` poll.
+    pub fn trigger_shutdown(&self) {
+        let _ = self.shutdown_tx.send(true);
     }
 
-    pub fn subscribe_telemetry(&self) -> watch::Receiver<Option<pb::TelemetryFrame>> {
-        self.telemetry_tx.subscribe()
+    pub fn subscribe_shutdown(&self) -> watch::Receiver<bool> {
+        self.shutdown_tx.subscribe()
+    }
+
+    /// Records an outbound envelope in `session_id`'s replay ring.
+    pub async fn record_outbound(&self, session_id: &str, env: &pb::Envelope) {
+        let mut buffers = self.replay_buffers.lock().await;
+        if !buffers.contains_key(session_id) {
+            let mut order = self.replay_session_order.lock().await;
+            order.push_back(session_id.to_string());
+            while order.len() > self.relay_cfg.max_replay_sessions {
+                if let Some(oldest) = order.pop_front() {
+                    buffers.remove(&oldest);
+                }
+            }
+        }
+        buffers
+            .entry(session_id.to_string())
+            .or_insert_with(|| ReplayBuffer::new(self.relay_cfg.replay_buffer_frames))
+            .push(env.clone());
+    }
+
+    /// Drops frames with `seq <= ack` from `session_id`'s replay ring, as
+    /// reported by the peer's own envelope `ack` field.
+    pub async fn evict_acked(&self, session_id: &str, ack: u64) {
+        let mut buffers = self.replay_buffers.lock().await;
+        if let Some(buf) = buffers.get_mut(session_id) {
+            buf.evict_acked(ack);
+        }
+    }
+
+    /// Frames to resend for a resuming session, or `None` if resumption
+    /// must be rejected (unknown session, or frames already evicted).
+    pub async fn take_replay_frames(&self, session_id: &str, ack: u64) -> Option<Vec<pb::Envelope>> {
+        let buffers = self.replay_buffers.lock().await;
+        buffers.get(session_id).and_then(|buf| buf.frames_after(ack))
+    }
+
+    /// Highest `seq` ever sent under `session_id`, so a resumed session can
+    /// continue numbering frames instead of restarting from zero.
+    pub async fn last_seq(&self, session_id: &str) -> Option<u64> {
+        let buffers = self.replay_buffers.lock().await;
+        buffers.get(session_id).map(|buf| buf.last_seq)
+    }
+
+    /// Sequenced telemetry starting at `seq` (inclusive): drains whatever
+    /// the history ring still has buffered at or after `seq`, then
+    /// continues live. Pass `0` (or any seq at or before the oldest
+    /// buffered one) to get everything the ring currently holds.
+    ///
+    /// There used to be a `subscribe_telemetry()` latest-only convenience
+    /// wrapper alongside this (a `watch` over the newest frame, no
+    /// sequencing). `acquire_orchestrator_slot`'s per-session `Interest`
+    /// channels (e.g. `Interest::AnyTelemetry`) now cover that same
+    /// "just give me telemetry" case with server-side filtering on top, so
+    /// the wrapper was dropped rather than kept alongside as a second,
+    /// unsequenced path into the same data.
+    pub async fn subscribe_telemetry_from(&self, seq: u64) -> TelemetrySubscription {
+        // Subscribe before taking the history snapshot so nothing published
+        // in between is missed; `recv`'s `last_seq` tracking discards the
+        // resulting overlap instead.
+        let live = self.telemetry_broadcast.subscribe();
+        let buffered: VecDeque<TelemetryEvent> = {
+            let history = self.telemetry_history.lock().await;
+            history.iter().filter(|e| e.seq >= seq).cloned().collect()
+        };
+        TelemetrySubscription {
+            buffered,
+            live,
+            last_seq: seq.saturating_sub(1),
+            history: Arc::clone(&self.telemetry_history),
+        }
     }
 
     pub fn action_queue_size(&self) -> usize {
@@ -57,7 +375,7 @@ impl RelayHub {
         &self.relay_cfg.primary_game_agent_id
     }
 
-    pub fn publish_telemetry(&self, telemetry: &pb::TelemetryFrame) {
+    pub async fn publish_telemetry(&self, telemetry: &pb::TelemetryFrame) {
         if self.relay_cfg.min_relay_interval_ms > 0 {
             let now = mono_ms();
             let last = self.last_relay_mono_ms.load(Ordering::Relaxed);
@@ -66,12 +384,40 @@ impl RelayHub {
             }
             self.last_relay_mono_ms.store(now, Ordering::Relaxed);
         }
-        self.telemetry_tx.send_replace(Some(telemetry.clone()));
+        let event = TelemetryEvent {
+            seq: self.telemetry_next_seq.fetch_add(1, Ordering::Relaxed),
+            frame: telemetry.clone(),
+        };
+        {
+            let mut history = self.telemetry_history.lock().await;
+            if history.len() >= self.relay_cfg.telemetry_history_len.max(1) {
+                history.pop_front();
+            }
+            history.push_back(event.clone());
+        }
+        // No live subscribers is the common case and not an error.
+        let _ = self.telemetry_broadcast.send(event.clone());
+
+        for entry in self.telemetry_any.iter() {
+            let _ = entry.value().try_send(event.clone());
+        }
+        for entry in self.telemetry_hp_at_most.iter() {
+            let (threshold, tx) = entry.value();
+            if telemetry.hp <= *threshold {
+                let _ = tx.try_send(event.clone());
+            }
+        }
     }
 
+    /// Acquires a subscriber slot and, for each declared `interests` entry,
+    /// wires up the matching index so `publish_telemetry`/
+    /// `route_action_result` forward only events that match. An orchestrator
+    /// that declares no interests gets a slot whose `OrchestratorInterestHandles`
+    /// channels simply never receive anything.
     pub fn acquire_orchestrator_slot(
         self: &Arc<Self>,
-    ) -> std::result::Result<OrchestratorSlot, OrchestratorAcquireError> {
+        interests: Vec<Interest>,
+    ) -> std::result::Result<(OrchestratorSlot, OrchestratorInterestHandles), OrchestratorAcquireError> {
         if !self.relay_cfg.allow_orchestrator_subscribe {
             return Err(OrchestratorAcquireError::NotAllowed);
         }
@@ -92,35 +438,119 @@ impl RelayHub {
                 )
                 .is_ok()
             {
-                return Ok(OrchestratorSlot {
-                    hub: Arc::clone(self),
-                });
+                let slot_id = self.next_slot_id.fetch_add(1, Ordering::Relaxed);
+                let (telemetry_tx, telemetry_rx) = mpsc::channel(self.relay_cfg.action_queue_size);
+                let (action_result_tx, action_result_rx) = mpsc::channel(self.relay_cfg.action_queue_size);
+
+                for interest in &interests {
+                    match interest {
+                        Interest::AnyTelemetry => {
+                            self.telemetry_any.insert(slot_id, telemetry_tx.clone());
+                        }
+                        Interest::TelemetryHpAtMost(threshold) => {
+                            self.telemetry_hp_at_most.insert(slot_id, (*threshold, telemetry_tx.clone()));
+                        }
+                        Interest::ActionResultForAgent(agent_id) => {
+                            self.action_result_by_agent
+                                .entry(agent_id.clone())
+                                .or_insert_with(DashMap::new)
+                                .insert(slot_id, action_result_tx.clone());
+                        }
+                    }
+                }
+                self.slot_interests.insert(slot_id, interests);
+
+                return Ok((
+                    OrchestratorSlot {
+                        hub: Arc::clone(self),
+                        slot_id,
+                    },
+                    OrchestratorInterestHandles {
+                        telemetry_rx,
+                        action_result_rx,
+                    },
+                ));
             }
         }
     }
 
-    pub async fn attach_primary_game_sender(
-        &self,
-        sender: mpsc::Sender<pb::ActionRequest>,
-        agent_id: &str,
-    ) -> Result<()> {
-        if !self.is_primary_game_agent(agent_id) {
-            anyhow::bail!("non-primary game agent cannot attach action sender");
+    /// Attaches `agent_id`'s action inbox, replacing any previous sender for
+    /// the same agent (a reconnect). Wakes anyone in `enqueue_action` who's
+    /// currently parked waiting for this agent to show up, and replays any
+    /// `held_actions` left over from that agent's last disconnect (see
+    /// `detach_agent`) through the new sender, keeping their original
+    /// `reply_tx` so callers never notice the gap.
+    pub fn attach_agent(&self, agent_id: &str, sender: mpsc::Sender<pb::ActionRequest>) {
+        self.agents.insert(agent_id.to_string(), AgentSlot { sender: sender.clone() });
+        if let Some(notify) = self.agent_attach_notify.get(agent_id) {
+            notify.notify_waiters();
         }
 
-        let mut slot = self.primary_game_sender.lock().await;
-        if slot.is_some() {
-            anyhow::bail!("primary game sender already attached");
+        let held_ids: Vec<String> = self
+            .held_actions
+            .iter()
+            .filter(|entry| entry.value().agent_id == agent_id)
+            .map(|entry| entry.key().clone())
+            .collect();
+        for request_id in held_ids {
+            let Some((_, held)) = self.held_actions.remove(&request_id) else {
+                continue;
+            };
+            match sender.try_send(held.request.clone()) {
+                Ok(()) => {
+                    self.pending_actions.insert(
+                        request_id,
+                        PendingAction {
+                            agent_id: held.agent_id,
+                            request: held.request,
+                            reply_tx: held.reply_tx,
+                            acked: false,
+                        },
+                    );
+                }
+                Err(_) => {
+                    let reply_tx = held.reply_tx;
+                    tokio::spawn(Self::fail_one(request_id, reply_tx, "failed to replay buffered action on reconnect"));
+                }
+            }
         }
-        *slot = Some(sender);
-        Ok(())
     }
 
-    pub async fn detach_primary_game_sender(&self) {
-        let mut slot = self.primary_game_sender.lock().await;
-        *slot = None;
-        drop(slot);
-        self.fail_all_pending("primary game client disconnected").await;
+    pub async fn detach_agent(&self, agent_id: &str) {
+        self.agents.remove(agent_id);
+
+        let pending_ids: Vec<String> = self
+            .pending_actions
+            .iter()
+            .filter(|entry| entry.value().agent_id == agent_id)
+            .map(|entry| entry.key().clone())
+            .collect();
+
+        let mut to_fail = Vec::new();
+        for request_id in pending_ids {
+            let Some((_, pending)) = self.pending_actions.remove(&request_id) else {
+                continue;
+            };
+            // Already-acked actions are already running on the agent side;
+            // they can't be safely replayed, so they fail immediately same
+            // as before the grace window existed.
+            if self.relay_cfg.reconnect_grace_ms == 0 || pending.acked {
+                to_fail.push((request_id, pending.reply_tx));
+            } else {
+                self.held_actions.insert(
+                    request_id,
+                    HeldAction {
+                        agent_id: pending.agent_id,
+                        request: pending.request,
+                        reply_tx: pending.reply_tx,
+                        deadline_ms: mono_ms() + self.relay_cfg.reconnect_grace_ms,
+                    },
+                );
+            }
+        }
+        for (request_id, reply_tx) in to_fail {
+            Self::fail_one(request_id, reply_tx, "agent disconnected").await;
+        }
     }
 
     pub async fn enqueue_action(
@@ -131,40 +561,41 @@ impl RelayHub {
         if req.request_id.trim().is_empty() {
             anyhow::bail!("request_id must not be empty");
         }
-
-        if !req.target_agent_id.trim().is_empty() && req.target_agent_id != self.relay_cfg.primary_game_agent_id {
-            anyhow::bail!(
-                "target_agent_id={} does not match primary_game_agent_id={}",
-                req.target_agent_id,
-                self.relay_cfg.primary_game_agent_id
-            );
+        if req.target_agent_id.trim().is_empty() {
+            anyhow::bail!("target_agent_id must not be empty");
         }
 
-        let sender_opt = { self.primary_game_sender.lock().await.clone() };
-        let Some(primary_sender) = sender_opt else {
-            anyhow::bail!("primary game client is not connected");
+        let agent_sender = match self.agents.get(&req.target_agent_id).map(|slot| slot.sender.clone()) {
+            Some(sender) => sender,
+            None => self.await_agent_attach(&req.target_agent_id).await?,
         };
 
         let request_id = req.request_id.clone();
-        {
-            let mut pending = self.pending_actions.lock().await;
-            pending.insert(request_id.clone(), reply_tx);
-        }
+        let target_agent_id = req.target_agent_id.clone();
+        self.pending_actions.insert(
+            request_id.clone(),
+            PendingAction {
+                agent_id: target_agent_id,
+                request: req.clone(),
+                reply_tx,
+                acked: false,
+            },
+        );
 
         let send_res = tokio::time::timeout(
             std::time::Duration::from_millis(self.relay_cfg.action_send_timeout_ms),
-            primary_sender.send(req),
+            agent_sender.send(req),
         )
         .await;
 
         match send_res {
             Ok(Ok(())) => Ok(()),
             Ok(Err(_)) => {
-                self.pending_actions.lock().await.remove(&request_id);
-                anyhow::bail!("primary game action queue closed")
+                self.pending_actions.remove(&request_id);
+                anyhow::bail!("target agent action queue closed")
             }
             Err(_) => {
-                self.pending_actions.lock().await.remove(&request_id);
+                self.pending_actions.remove(&request_id);
                 anyhow::bail!(
                     "action enqueue timeout after {}ms",
                     self.relay_cfg.action_send_timeout_ms
@@ -173,60 +604,123 @@ impl RelayHub {
         }
     }
 
+    /// Parks until `agent_id` attaches (re-checking before and after the
+    /// wait, since the attach could race the subscribe), or fails fast if
+    /// `agent_attach_wait_ms` is `0`.
+    async fn await_agent_attach(&self, agent_id: &str) -> Result<mpsc::Sender<pb::ActionRequest>> {
+        if let Some(slot) = self.agents.get(agent_id) {
+            return Ok(slot.sender.clone());
+        }
+        if self.relay_cfg.agent_attach_wait_ms == 0 {
+            anyhow::bail!("target agent {agent_id} is not connected");
+        }
+
+        let notify = Arc::clone(
+            self.agent_attach_notify
+                .entry(agent_id.to_string())
+                .or_insert_with(|| Arc::new(Notify::new()))
+                .value(),
+        );
+
+        // Re-check after registering interest, in case the agent attached
+        // between the first lookup above and here.
+        if let Some(slot) = self.agents.get(agent_id) {
+            return Ok(slot.sender.clone());
+        }
+
+        let waited = tokio::time::timeout(
+            std::time::Duration::from_millis(self.relay_cfg.agent_attach_wait_ms),
+            notify.notified(),
+        )
+        .await;
+        if waited.is_err() {
+            anyhow::bail!("target agent {agent_id} did not attach within {}ms", self.relay_cfg.agent_attach_wait_ms);
+        }
+
+        self.agents
+            .get(agent_id)
+            .map(|slot| slot.sender.clone())
+            .ok_or_else(|| anyhow::anyhow!("target agent {agent_id} is not connected"))
+    }
+
     pub async fn route_action_ack(&self, ack: &pb::ActionAck) {
-        let maybe_reply_tx = {
-            let mut pending = self.pending_actions.lock().await;
-            if ack.accepted {
-                pending.get(&ack.request_id).cloned()
-            } else {
-                pending.remove(&ack.request_id)
-            }
+        let maybe_reply_tx = if ack.accepted {
+            self.pending_actions.get_mut(&ack.request_id).map(|mut p| {
+                p.acked = true;
+                p.reply_tx.clone()
+            })
+        } else {
+            self.pending_actions.remove(&ack.request_id).map(|(_, p)| p.reply_tx)
         };
 
         if let Some(reply_tx) = maybe_reply_tx {
             if reply_tx.send(ActionRelayFrame::Ack(ack.clone())).await.is_err() {
-                self.pending_actions.lock().await.remove(&ack.request_id);
+                self.pending_actions.remove(&ack.request_id);
             }
         }
     }
 
     pub async fn route_action_result(&self, result: &pb::ActionResult) {
-        let maybe_reply_tx = self.pending_actions.lock().await.remove(&result.request_id);
-        if let Some(reply_tx) = maybe_reply_tx {
-            let _ = reply_tx.send(ActionRelayFrame::Result(result.clone())).await;
+        let maybe_pending = self.pending_actions.remove(&result.request_id).map(|(_, p)| p);
+        if let Some(pending) = maybe_pending {
+            let _ = pending.reply_tx.send(ActionRelayFrame::Result(result.clone())).await;
+
+            if let Some(subs) = self.action_result_by_agent.get(&pending.agent_id) {
+                for entry in subs.iter() {
+                    let _ = entry.value().try_send(result.clone());
+                }
+            }
         }
     }
 
-    async fn fail_all_pending(&self, reason: &str) {
-        let drained = {
-            let mut pending = self.pending_actions.lock().await;
-            pending.drain().collect::<Vec<_>>()
+    /// Sends the synthetic `ActionAck{accepted: false}` + timeout
+    /// `ActionResult` pair that stands in for a request that will never get
+    /// a real reply, whether because its agent disconnected with no grace
+    /// window, it was already acked when the agent dropped, or it sat in
+    /// `held_actions` past its `reconnect_grace_ms` deadline.
+    async fn fail_one(request_id: String, reply_tx: mpsc::Sender<ActionRelayFrame>, reason: &str) {
+        let ack = pb::ActionAck {
+            request_id: request_id.clone(),
+            accepted: false,
+            reason: reason.to_string(),
         };
-        for (request_id, reply_tx) in drained {
-            let ack = pb::ActionAck {
-                request_id: request_id.clone(),
-                accepted: false,
-                reason: reason.to_string(),
-            };
-            let result = pb::ActionResult {
-                request_id,
-                status: pb::ActionStatus::ActionStatusTimeout as i32,
-                detail: reason.to_string(),
-                final_state_version: 0,
-            };
-            let _ = reply_tx.send(ActionRelayFrame::Ack(ack)).await;
-            let _ = reply_tx.send(ActionRelayFrame::Result(result)).await;
-        }
+        let result = pb::ActionResult {
+            request_id,
+            status: pb::ActionStatus::ActionStatusTimeout as i32,
+            detail: reason.to_string(),
+            final_state_version: 0,
+        };
+        let _ = reply_tx.send(ActionRelayFrame::Ack(ack)).await;
+        let _ = reply_tx.send(ActionRelayFrame::Result(result)).await;
     }
 }
 
 pub struct OrchestratorSlot {
     hub: Arc<RelayHub>,
+    slot_id: u64,
 }
 
 impl Drop for OrchestratorSlot {
     fn drop(&mut self) {
         self.hub.orchestrator_count.fetch_sub(1, Ordering::SeqCst);
+
+        if let Some((_, interests)) = self.hub.slot_interests.remove(&self.slot_id) {
+            for interest in interests {
+                match interest {
+                    Interest::AnyTelemetry => {
+                        self.hub.telemetry_any.remove(&self.slot_id);
+                    }
+                    Interest::TelemetryHpAtMost(_) => {
+                        self.hub.telemetry_hp_at_most.remove(&self.slot_id);
+                    }
+                    Interest::ActionResultForAgent(agent_id) => {
+                        if let Some(subs) = self.hub.action_result_by_agent.get(&agent_id) {
+                            subs.remove(&self.slot_id);
+                        }
+                    }
+                }
+            }
+        }
     }
 }
 
@@ -1,7 +1,10 @@
 use anyhow::{Context, Result};
+use bytes::BytesMut;
 use futures_util::{SinkExt, StreamExt};
 use prost::Message;
+use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::net::TcpStream;
 use tokio::sync::{mpsc, watch};
 use tokio_rustls::server::TlsStream;
@@ -10,17 +13,102 @@ use tokio_tungstenite::{accept_async_with_config, tungstenite::protocol::Message
 use tracing::{info, warn};
 use uuid::Uuid;
 
+use crate::config::LimitsConfig;
 use crate::pb::bridge_v1 as pb;
-use crate::relay::{ActionRelayFrame, OrchestratorAcquireError, RelayHub};
+use crate::relay::{ActionRelayFrame, Interest, OrchestratorAcquireError, RelayHub, TelemetryEvent};
 
 const PROTOCOL_VERSION: u32 = 1;
-const SERVER_NEGOTIABLE_CAPABILITIES: [i32; 4] = [
+const SERVER_NEGOTIABLE_CAPABILITIES: [i32; 5] = [
     pb::Capability::CapTelemetryV1 as i32,
     pb::Capability::CapTimesyncV1 as i32,
     pb::Capability::CapActionsV1 as i32,
     pb::Capability::CapHelloAckV1 as i32,
+    pb::Capability::CapCompressionV1 as i32,
 ];
 
+/// One-byte discriminator prefixed to a `WsMessage::Binary` payload once
+/// compression has been negotiated for the session, so a stream can mix
+/// compressed and uncompressed envelopes unambiguously from that point on.
+/// Frames sent before negotiation (the hello exchange) or on a session that
+/// never negotiated `CapCompressionV1` carry no prefix at all — see
+/// `frame_payload`/`decode_envelope`.
+const COMPRESSION_NONE: u8 = 0;
+const COMPRESSION_ZSTD: u8 = 1;
+
+/// Negotiated payload compression, confirmed via `HelloAck` per
+/// `CapCompressionV1` (mirrors devp2p's post-hello capability gating).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CompressionKind {
+    Zstd,
+}
+
+impl CompressionKind {
+    fn discriminator(self) -> u8 {
+        match self {
+            CompressionKind::Zstd => COMPRESSION_ZSTD,
+        }
+    }
+}
+
+/// An outbound reliable frame (action dispatch/ack/result) awaiting the
+/// peer's `ack` to catch up past its `seq`; resent on a timer until then.
+struct InFlightFrame {
+    env: pb::Envelope,
+    attempts: u32,
+    last_sent: Instant,
+}
+
+/// Per-session staging queue for coalescable (telemetry) frames. A slow
+/// reader should never stall other sessions, and a burst that outruns the
+/// peer should drop its oldest stale frames rather than grow unbounded —
+/// actions, acks, and errors never pass through here, they go out (and are
+/// tracked for retransmission) immediately via `send_envelope`.
+struct OutboundQueue {
+    scratch: BytesMut,
+    queued: VecDeque<Vec<u8>>,
+    buffered_bytes: usize,
+    high_water_bytes: usize,
+    dropped_frames: u64,
+}
+
+impl OutboundQueue {
+    fn new(high_water_bytes: usize) -> Self {
+        Self {
+            scratch: BytesMut::new(),
+            queued: VecDeque::new(),
+            buffered_bytes: 0,
+            high_water_bytes,
+            dropped_frames: 0,
+        }
+    }
+
+    /// Encodes `env` into the reusable scratch buffer (no fresh `Vec` per
+    /// call) and enqueues it, dropping the oldest queued frame(s) first if
+    /// that's needed to stay under `high_water_bytes`.
+    fn enqueue(&mut self, env: &pb::Envelope) -> Result<()> {
+        self.scratch.clear();
+        env.encode(&mut self.scratch).context("encode queued env")?;
+        let encoded = self.scratch.split().to_vec();
+
+        while self.buffered_bytes + encoded.len() > self.high_water_bytes {
+            let Some(dropped) = self.queued.pop_front() else {
+                break;
+            };
+            self.buffered_bytes -= dropped.len();
+            self.dropped_frames += 1;
+        }
+        self.buffered_bytes += encoded.len();
+        self.queued.push_back(encoded);
+        Ok(())
+    }
+
+    fn pop(&mut self) -> Option<Vec<u8>> {
+        let encoded = self.queued.pop_front()?;
+        self.buffered_bytes -= encoded.len();
+        Some(encoded)
+    }
+}
+
 pub struct SessionState {
     pub session_id: String,
     pub server_seq: u64,
@@ -30,10 +118,28 @@ pub struct SessionState {
     pub peer_caps: Vec<i32>,
     pub send_timeout_ms: u64,
     pub is_primary_game: bool,
+    /// Set once `attach_agent` succeeds for a `PeerRoleGameClient` session
+    /// (any agent, not just the primary one), so action ack/result routing
+    /// and the matching `detach_agent` on session end are gated correctly
+    /// under multi-agent relay.
+    pub agent_attached: bool,
+    /// `None` until `HelloAck` confirms both peers advertised
+    /// `CapCompressionV1`; the hello exchange itself is always uncompressed.
+    pub compression: Option<CompressionKind>,
+    compression_min_bytes: usize,
+    /// Updated on every received ping, pong, or decoded envelope; checked
+    /// against `ping_timeout_ms` to detect a half-open connection.
+    pub last_rx: Instant,
+    /// Reliable frames sent but not yet covered by the peer's `ack`, keyed
+    /// by their `seq`. Telemetry/heartbeats never enter this map.
+    in_flight: HashMap<u64, InFlightFrame>,
+    /// Staged coalescable (telemetry) frames, flushed after every send that
+    /// enqueues into it. See `OutboundQueue`.
+    outbound: OutboundQueue,
 }
 
 impl SessionState {
-    pub fn new(send_timeout_ms: u64) -> Self {
+    pub fn new(send_timeout_ms: u64, compression_min_bytes: usize, outbound_high_water_bytes: usize) -> Self {
         Self {
             session_id: Uuid::new_v4().to_string(),
             server_seq: 0,
@@ -43,9 +149,21 @@ impl SessionState {
             peer_caps: Vec::new(),
             send_timeout_ms,
             is_primary_game: false,
+            agent_attached: false,
+            compression: None,
+            compression_min_bytes,
+            last_rx: Instant::now(),
+            in_flight: HashMap::new(),
+            outbound: OutboundQueue::new(outbound_high_water_bytes),
         }
     }
 
+    /// `(buffered_bytes, dropped_frames)` for this session's outbound
+    /// coalescable queue, for operator visibility into lagging sessions.
+    pub fn outbound_stats(&self) -> (usize, u64) {
+        (self.outbound.buffered_bytes, self.outbound.dropped_frames)
+    }
+
     fn supports_hello_ack(&self) -> bool {
         self.peer_caps
             .iter()
@@ -55,15 +173,13 @@ impl SessionState {
 
 pub async fn run_ws_session(
     tls_stream: TlsStream<TcpStream>,
-    max_ws_message_bytes: usize,
-    hello_timeout_ms: u64,
-    send_timeout_ms: u64,
+    limits: &LimitsConfig,
     relay_hub: Arc<RelayHub>,
 ) -> Result<()> {
     let ws_cfg = WebSocketConfig {
         max_send_queue: Some(32),
-        max_message_size: Some(max_ws_message_bytes),
-        max_frame_size: Some(max_ws_message_bytes),
+        max_message_size: Some(limits.max_ws_message_bytes),
+        max_frame_size: Some(limits.max_ws_message_bytes),
         accept_unmasked_frames: false,
     };
 
@@ -71,11 +187,15 @@ pub async fn run_ws_session(
         .await
         .context("websocket accept")?;
 
-    let mut st = SessionState::new(send_timeout_ms);
+    let mut st = SessionState::new(
+        limits.send_timeout_ms,
+        limits.compression_min_bytes,
+        limits.outbound_high_water_bytes,
+    );
     info!(session_id = %st.session_id, "ws connected");
 
     let hello_msg = match tokio::time::timeout(
-        std::time::Duration::from_millis(hello_timeout_ms),
+        std::time::Duration::from_millis(limits.hello_timeout_ms),
         ws.next(),
     )
     .await
@@ -85,24 +205,25 @@ pub async fn run_ws_session(
             .context("ws read")?
             .ok_or_else(|| anyhow::anyhow!("ws closed before hello"))?,
         Err(_) => {
-            send_error(
+            send_disconnect(
                 &mut ws,
                 &mut st,
-                pb::ErrorCode::ErrorCodeTimeout,
+                &relay_hub,
+                pb::DisconnectReason::DisconnectReasonIdleTimeout,
                 "hello timeout",
-                "hello-timeout",
             )
             .await?;
             anyhow::bail!("hello timeout");
         }
     };
 
-    let hello_env = match decode_envelope(hello_msg) {
+    let hello_env = match decode_envelope(&st, hello_msg) {
         Ok(v) => v,
         Err(e) => {
             send_error(
                 &mut ws,
                 &mut st,
+                &relay_hub,
                 pb::ErrorCode::ErrorCodeDecodeFailed,
                 "invalid hello envelope",
                 "hello-decode",
@@ -117,6 +238,7 @@ pub async fn run_ws_session(
         send_error(
             &mut ws,
             &mut st,
+            &relay_hub,
             pb::ErrorCode::ErrorCodeProtocolViolation,
             "protocol_version mismatch",
             "hello-proto",
@@ -131,6 +253,7 @@ pub async fn run_ws_session(
             send_error(
                 &mut ws,
                 &mut st,
+                &relay_hub,
                 pb::ErrorCode::ErrorCodeProtocolViolation,
                 "expected hello",
                 "hello-shape",
@@ -152,84 +275,138 @@ pub async fn run_ws_session(
             "ignored client handshake_id; bridge enforces server-side handshake_id"
         );
     }
+
+    // Resumption: a reconnecting peer carries its prior session_id in the
+    // hello envelope itself (not a fresh, server-minted one) plus its last
+    // acknowledged server seq in `ack`. Only attempted for post-HelloAck
+    // peers, since resumption relies on that negotiated handshake shape.
+    //
+    // There's no wire field yet that distinguishes "this really is a
+    // reconnect" from "this is a fresh connection that happens to carry a
+    // non-empty session_id" (the bundled orchestrator client always sets
+    // one — see `bridge_client.rs`), so a lookup miss below can't be
+    // trusted as "the peer's session expired" — it's just as likely a
+    // brand-new session. Fall through to starting a fresh one in both
+    // cases rather than rejecting the connection outright.
+    let resume_requested = supports_hello_ack && !hello_env.session_id.is_empty();
+    let mut resume_frames: Option<Vec<pb::Envelope>> = None;
+    if resume_requested {
+        match relay_hub
+            .take_replay_frames(&hello_env.session_id, hello_env.ack)
+            .await
+        {
+            Some(frames) => {
+                info!(
+                    resumed_session_id = %hello_env.session_id,
+                    replay_ack = hello_env.ack,
+                    replayed_frames = frames.len(),
+                    "resuming session"
+                );
+                st.session_id = hello_env.session_id.clone();
+                if let Some(last_seq) = relay_hub.last_seq(&hello_env.session_id).await {
+                    st.server_seq = last_seq;
+                }
+                resume_frames = Some(frames);
+            }
+            None => {
+                warn!(
+                    requested_session_id = %hello_env.session_id,
+                    requested_ack = hello_env.ack,
+                    "no resumable session found, starting a new session instead"
+                );
+            }
+        }
+    }
     let handshake_id = st.session_id.clone();
 
     info!(
         agent_id = %hello.agent_id,
         client_version = %hello.client_version,
         role = ?st.peer_role,
+        resumed = resume_frames.is_some(),
         "hello received"
     );
 
     match st.peer_role {
         pb::PeerRole::PeerRoleGameClient => {
-            let mut action_rx: Option<mpsc::Receiver<pb::ActionRequest>> = None;
-            if relay_hub.is_primary_game_agent(&hello.agent_id) {
-                let (tx, rx) = mpsc::channel(relay_hub.action_queue_size());
-                if let Err(e) = relay_hub
-                    .attach_primary_game_sender(tx, &hello.agent_id)
-                    .await
-                    .context("attach primary game sender")
-                {
-                    send_handshake_reject(
-                        &mut ws,
-                        &mut st,
-                        supports_hello_ack,
-                        &handshake_id,
-                        "primary game sender is unavailable",
-                    )
-                    .await?;
-                    return Err(e);
-                }
-                st.is_primary_game = true;
-                action_rx = Some(rx);
-            } else {
-                warn!(
-                    agent_id = %hello.agent_id,
-                    primary_game_agent_id = %relay_hub.primary_game_agent_id(),
-                    "non-primary game client connected; telemetry/action relay disabled for this session"
-                );
-            }
+            let (tx, rx) = mpsc::channel(relay_hub.action_queue_size());
+            relay_hub.attach_agent(&hello.agent_id, tx);
+            st.agent_attached = true;
+            st.is_primary_game = relay_hub.is_primary_game_agent(&hello.agent_id);
+            let mut action_rx = Some(rx);
 
-            send_handshake_ok(&mut ws, &mut st, supports_hello_ack, &handshake_id).await?;
-            let run_res = run_game_session_loop(&mut ws, &mut st, &relay_hub, action_rx.as_mut()).await;
-            if st.is_primary_game {
-                relay_hub.detach_primary_game_sender().await;
+            send_handshake_ok(&mut ws, &mut st, &relay_hub, supports_hello_ack, &handshake_id).await?;
+            if let Some(frames) = resume_frames.take() {
+                replay_resumed_frames(&mut ws, &mut st, frames).await?;
+            }
+            let run_res = run_game_session_loop(&mut ws, &mut st, &relay_hub, action_rx.as_mut(), limits).await;
+            if st.agent_attached {
+                relay_hub.detach_agent(&hello.agent_id).await;
             }
             run_res?;
         }
         pb::PeerRole::PeerRoleOrchestrator => {
-            let _slot = match relay_hub.acquire_orchestrator_slot() {
-                Ok(slot) => slot,
+            // No Hello-schema support yet for an orchestrator to declare its
+            // own interest patterns over the wire, so every session declares
+            // `AnyTelemetry` for now (the same unfiltered firehose it got
+            // before interests existed) and is delivered over the matching
+            // `OrchestratorInterestHandles::telemetry_rx`. Once a wire field
+            // exists for negotiated patterns, only this `interests` vec needs
+            // to change — the slot/index/delivery plumbing already branches
+            // on whatever's declared here.
+            let interests = vec![Interest::AnyTelemetry];
+            let (_slot, interest_handles) = match relay_hub.acquire_orchestrator_slot(interests) {
+                Ok(pair) => pair,
                 Err(OrchestratorAcquireError::NotAllowed) => {
                     send_handshake_reject(
                         &mut ws,
                         &mut st,
+                        &relay_hub,
                         supports_hello_ack,
                         &handshake_id,
                         "orchestrator subscriptions are disabled",
                     )
                     .await?;
+                    send_disconnect(
+                        &mut ws,
+                        &mut st,
+                        &relay_hub,
+                        pb::DisconnectReason::DisconnectReasonProtocolViolation,
+                        "orchestrator subscriptions are disabled",
+                    )
+                    .await?;
                     anyhow::bail!("orchestrator subscriptions are disabled");
                 }
                 Err(OrchestratorAcquireError::LimitReached) => {
                     send_handshake_reject(
                         &mut ws,
                         &mut st,
+                        &relay_hub,
                         supports_hello_ack,
                         &handshake_id,
                         "orchestrator subscription limit reached",
                     )
                     .await?;
+                    send_disconnect(
+                        &mut ws,
+                        &mut st,
+                        &relay_hub,
+                        pb::DisconnectReason::DisconnectReasonLimitReached,
+                        "orchestrator subscription limit reached",
+                    )
+                    .await?;
                     anyhow::bail!("orchestrator subscription limit reached");
                 }
             };
 
-            let mut telemetry_rx = relay_hub.subscribe_telemetry();
+            let mut telemetry_rx = interest_handles.telemetry_rx;
             let (action_reply_tx, mut action_reply_rx) =
                 mpsc::channel::<ActionRelayFrame>(relay_hub.action_queue_size());
 
-            send_handshake_ok(&mut ws, &mut st, supports_hello_ack, &handshake_id).await?;
+            send_handshake_ok(&mut ws, &mut st, &relay_hub, supports_hello_ack, &handshake_id).await?;
+            if let Some(frames) = resume_frames.take() {
+                replay_resumed_frames(&mut ws, &mut st, frames).await?;
+            }
             run_orchestrator_session_loop(
                 &mut ws,
                 &mut st,
@@ -237,6 +414,7 @@ pub async fn run_ws_session(
                 &mut telemetry_rx,
                 &mut action_reply_rx,
                 &action_reply_tx,
+                limits,
             )
             .await?;
         }
@@ -244,6 +422,7 @@ pub async fn run_ws_session(
             send_handshake_reject(
                 &mut ws,
                 &mut st,
+                &relay_hub,
                 supports_hello_ack,
                 &handshake_id,
                 "unsupported peer role",
@@ -256,13 +435,47 @@ pub async fn run_ws_session(
     Ok(())
 }
 
+/// Resends previously-sent frames to a resuming peer, preserving each
+/// frame's original `seq`/`ack` rather than re-stamping it via
+/// `send_envelope` (which would also re-record it into the replay ring it
+/// just came from).
+async fn replay_resumed_frames(
+    ws: &mut tokio_tungstenite::WebSocketStream<TlsStream<TcpStream>>,
+    st: &mut SessionState,
+    frames: Vec<pb::Envelope>,
+) -> Result<()> {
+    for env in frames {
+        let mut buf = Vec::with_capacity(env.encoded_len());
+        env.encode(&mut buf).context("encode replayed env")?;
+        let framed = frame_payload(st, buf);
+        match tokio::time::timeout(
+            std::time::Duration::from_millis(st.send_timeout_ms),
+            ws.send(WsMessage::Binary(framed)),
+        )
+        .await
+        {
+            Ok(send_result) => {
+                send_result.context("ws send (replay)")?;
+            }
+            Err(_) => {
+                anyhow::bail!("ws send timeout (replay) after {}ms", st.send_timeout_ms);
+            }
+        }
+    }
+    Ok(())
+}
+
 async fn run_game_session_loop(
     ws: &mut tokio_tungstenite::WebSocketStream<TlsStream<TcpStream>>,
     st: &mut SessionState,
     relay_hub: &Arc<RelayHub>,
     action_rx: Option<&mut mpsc::Receiver<pb::ActionRequest>>,
+    limits: &LimitsConfig,
 ) -> Result<()> {
     if let Some(action_rx) = action_rx {
+        let mut ping_iv = new_ping_interval(limits.ping_interval_ms);
+        let mut retry_iv = new_retry_interval(limits.reliable_retry_interval_ms);
+        let mut shutdown_rx = relay_hub.subscribe_shutdown();
         loop {
             tokio::select! {
                 msg = ws.next() => {
@@ -278,13 +491,25 @@ async fn run_game_session_loop(
                     let Some(action_req) = action_req else {
                         break;
                     };
-                    send_envelope(ws, st, pb::envelope::Payload::ActionReq(action_req)).await?;
+                    send_envelope(ws, st, relay_hub, pb::envelope::Payload::ActionReq(action_req), true).await?;
+                }
+                _ = ping_iv.tick() => {
+                    if !on_ping_tick(ws, st, relay_hub, limits.ping_timeout_ms).await? {
+                        break;
+                    }
+                }
+                _ = retry_iv.tick() => {
+                    retry_in_flight(ws, st, relay_hub, limits.reliable_retry_interval_ms, limits.reliable_max_retries).await?;
+                }
+                _ = wait_for_shutdown(&mut shutdown_rx) => {
+                    send_disconnect(ws, st, relay_hub, pb::DisconnectReason::DisconnectReasonServerShutdown, "server shutting down").await?;
+                    break;
                 }
             }
         }
         Ok(())
     } else {
-        run_standard_session_loop(ws, st, relay_hub).await
+        run_standard_session_loop(ws, st, relay_hub, limits).await
     }
 }
 
@@ -292,11 +517,30 @@ async fn run_standard_session_loop(
     ws: &mut tokio_tungstenite::WebSocketStream<TlsStream<TcpStream>>,
     st: &mut SessionState,
     relay_hub: &Arc<RelayHub>,
+    limits: &LimitsConfig,
 ) -> Result<()> {
-    while let Some(msg) = ws.next().await {
-        let msg = msg.context("ws read")?;
-        if !handle_ws_message(ws, st, relay_hub, msg, None).await? {
-            break;
+    let mut ping_iv = new_ping_interval(limits.ping_interval_ms);
+    let mut shutdown_rx = relay_hub.subscribe_shutdown();
+    loop {
+        tokio::select! {
+            msg = ws.next() => {
+                let Some(msg) = msg else {
+                    break;
+                };
+                let msg = msg.context("ws read")?;
+                if !handle_ws_message(ws, st, relay_hub, msg, None).await? {
+                    break;
+                }
+            }
+            _ = ping_iv.tick() => {
+                if !on_ping_tick(ws, st, relay_hub, limits.ping_timeout_ms).await? {
+                    break;
+                }
+            }
+            _ = wait_for_shutdown(&mut shutdown_rx) => {
+                send_disconnect(ws, st, relay_hub, pb::DisconnectReason::DisconnectReasonServerShutdown, "server shutting down").await?;
+                break;
+            }
         }
     }
     Ok(())
@@ -306,10 +550,14 @@ async fn run_orchestrator_session_loop(
     ws: &mut tokio_tungstenite::WebSocketStream<TlsStream<TcpStream>>,
     st: &mut SessionState,
     relay_hub: &Arc<RelayHub>,
-    telemetry_rx: &mut watch::Receiver<Option<pb::TelemetryFrame>>,
+    telemetry_rx: &mut mpsc::Receiver<TelemetryEvent>,
     action_reply_rx: &mut mpsc::Receiver<ActionRelayFrame>,
     action_reply_tx: &mpsc::Sender<ActionRelayFrame>,
+    limits: &LimitsConfig,
 ) -> Result<()> {
+    let mut ping_iv = new_ping_interval(limits.ping_interval_ms);
+    let mut retry_iv = new_retry_interval(limits.reliable_retry_interval_ms);
+    let mut shutdown_rx = relay_hub.subscribe_shutdown();
     loop {
         tokio::select! {
             msg = ws.next() => {
@@ -321,10 +569,10 @@ async fn run_orchestrator_session_loop(
                     break;
                 }
             }
-            relay = wait_for_telemetry(telemetry_rx) => {
+            relay = telemetry_rx.recv() => {
                 match relay {
-                    Some(telemetry) => {
-                        send_envelope(ws, st, pb::envelope::Payload::Telemetry(telemetry)).await?;
+                    Some(event) => {
+                        queue_telemetry(ws, st, relay_hub, event.frame).await?;
                     }
                     None => {
                         warn!(session_id = %st.session_id, "telemetry relay channel closed");
@@ -338,25 +586,162 @@ async fn run_orchestrator_session_loop(
                 };
                 match action_reply {
                     ActionRelayFrame::Ack(ack) => {
-                        send_envelope(ws, st, pb::envelope::Payload::ActionAck(ack)).await?;
+                        send_envelope(ws, st, relay_hub, pb::envelope::Payload::ActionAck(ack), true).await?;
                     }
                     ActionRelayFrame::Result(result) => {
-                        send_envelope(ws, st, pb::envelope::Payload::ActionRes(result)).await?;
+                        send_envelope(ws, st, relay_hub, pb::envelope::Payload::ActionRes(result), true).await?;
                     }
                 }
             }
+            _ = ping_iv.tick() => {
+                if !on_ping_tick(ws, st, relay_hub, limits.ping_timeout_ms).await? {
+                    break;
+                }
+            }
+            _ = retry_iv.tick() => {
+                retry_in_flight(ws, st, relay_hub, limits.reliable_retry_interval_ms, limits.reliable_max_retries).await?;
+            }
+            _ = wait_for_shutdown(&mut shutdown_rx) => {
+                send_disconnect(ws, st, relay_hub, pb::DisconnectReason::DisconnectReasonServerShutdown, "server shutting down").await?;
+                break;
+            }
         }
     }
     Ok(())
 }
 
-async fn wait_for_telemetry(
-    telemetry_rx: &mut watch::Receiver<Option<pb::TelemetryFrame>>,
-) -> Option<pb::TelemetryFrame> {
-    if telemetry_rx.changed().await.is_err() {
-        return None;
+/// Builds a ping interval with its first (immediate) tick pre-consumed, so a
+/// freshly connected session isn't pinged before it's had a chance to talk.
+fn new_ping_interval(ping_interval_ms: u64) -> tokio::time::Interval {
+    let mut iv = tokio::time::interval(Duration::from_millis(ping_interval_ms));
+    iv.reset();
+    iv
+}
+
+/// Sends a WebSocket ping, unless `ping_timeout_ms` has already elapsed since
+/// the last received frame, in which case it sends a timeout `ErrorFrame`
+/// instead. Returns `false` when the caller should close the session.
+async fn on_ping_tick(
+    ws: &mut tokio_tungstenite::WebSocketStream<TlsStream<TcpStream>>,
+    st: &mut SessionState,
+    relay_hub: &Arc<RelayHub>,
+    ping_timeout_ms: u64,
+) -> Result<bool> {
+    if st.last_rx.elapsed() >= Duration::from_millis(ping_timeout_ms) {
+        warn!(session_id = %st.session_id, "liveness timeout, closing session");
+        send_error(
+            ws,
+            st,
+            relay_hub,
+            pb::ErrorCode::ErrorCodeTimeout,
+            "liveness timeout",
+            "liveness-timeout",
+        )
+        .await?;
+        return Ok(false);
+    }
+    ws.send(WsMessage::Ping(Vec::new())).await.context("ws ping")?;
+    Ok(true)
+}
+
+/// Builds the interval driving `retry_in_flight`, first tick pre-consumed
+/// for the same reason as `new_ping_interval`.
+fn new_retry_interval(reliable_retry_interval_ms: u64) -> tokio::time::Interval {
+    let mut iv = tokio::time::interval(Duration::from_millis(reliable_retry_interval_ms));
+    iv.reset();
+    iv
+}
+
+/// Resends any reliable frame not yet covered by the peer's `ack` whose
+/// `reliable_retry_interval_ms` has elapsed since it was last sent. A frame
+/// that has hit `reliable_max_retries` is given up on: the peer gets an
+/// `ErrorFrame`, and if it was an `ActionReq` the orchestrator waiting on it
+/// is unblocked with an `ActionAck{accepted: false}` via the `RelayHub`.
+async fn retry_in_flight(
+    ws: &mut tokio_tungstenite::WebSocketStream<TlsStream<TcpStream>>,
+    st: &mut SessionState,
+    relay_hub: &Arc<RelayHub>,
+    reliable_retry_interval_ms: u64,
+    reliable_max_retries: u32,
+) -> Result<()> {
+    let due: Vec<u64> = st
+        .in_flight
+        .iter()
+        .filter(|(_, f)| f.last_sent.elapsed() >= Duration::from_millis(reliable_retry_interval_ms))
+        .map(|(seq, _)| *seq)
+        .collect();
+
+    for seq in due {
+        let attempts = st.in_flight.get(&seq).map(|f| f.attempts).unwrap_or(0);
+        if attempts >= reliable_max_retries {
+            let Some(frame) = st.in_flight.remove(&seq) else {
+                continue;
+            };
+            warn!(
+                session_id = %st.session_id,
+                seq,
+                attempts,
+                "reliable frame delivery failed, giving up"
+            );
+            if let Some(pb::envelope::Payload::ActionReq(req)) = &frame.env.payload {
+                let nack = pb::ActionAck {
+                    request_id: req.request_id.clone(),
+                    accepted: false,
+                    reason: "bridge gave up retransmitting action request".to_string(),
+                };
+                relay_hub.route_action_ack(&nack).await;
+            }
+            send_error(
+                ws,
+                st,
+                relay_hub,
+                pb::ErrorCode::ErrorCodeTimeout,
+                "reliable frame delivery failed",
+                "reliable-delivery",
+            )
+            .await?;
+            continue;
+        }
+
+        let Some(frame) = st.in_flight.get_mut(&seq) else {
+            continue;
+        };
+        let mut buf = Vec::with_capacity(frame.env.encoded_len());
+        frame.env.encode(&mut buf).context("encode in-flight env")?;
+        let framed = frame_payload(st, buf);
+        match tokio::time::timeout(
+            std::time::Duration::from_millis(st.send_timeout_ms),
+            ws.send(WsMessage::Binary(framed)),
+        )
+        .await
+        {
+            Ok(send_result) => {
+                send_result.context("ws send (retry)")?;
+            }
+            Err(_) => {
+                anyhow::bail!("ws send timeout (retry) after {}ms", st.send_timeout_ms);
+            }
+        }
+        if let Some(frame) = st.in_flight.get_mut(&seq) {
+            frame.attempts += 1;
+            frame.last_sent = Instant::now();
+        }
+    }
+    Ok(())
+}
+
+/// Resolves once `RelayHub::trigger_shutdown` has been called, so the
+/// session loop's `select!` can notice a graceful shutdown and send a
+/// `Disconnect{ServerShutdown}` instead of dropping the connection silently.
+async fn wait_for_shutdown(shutdown_rx: &mut watch::Receiver<bool>) {
+    loop {
+        if *shutdown_rx.borrow() {
+            return;
+        }
+        if shutdown_rx.changed().await.is_err() {
+            return;
+        }
     }
-    telemetry_rx.borrow().clone()
 }
 
 async fn handle_ws_message(
@@ -370,6 +755,7 @@ async fn handle_ws_message(
         info!(session_id = %st.session_id, "ws close");
         return Ok(false);
     }
+    st.last_rx = Instant::now();
     if msg.is_ping() {
         return Ok(true);
     }
@@ -377,13 +763,14 @@ async fn handle_ws_message(
         return Ok(true);
     }
 
-    let env = match decode_envelope(msg) {
+    let env = match decode_envelope(st, msg) {
         Ok(v) => v,
         Err(e) => {
             warn!(session_id = %st.session_id, error = %e, "decode failed");
             send_error(
                 ws,
                 st,
+                relay_hub,
                 pb::ErrorCode::ErrorCodeDecodeFailed,
                 "decode failed",
                 "msg-decode",
@@ -397,6 +784,7 @@ async fn handle_ws_message(
         send_error(
             ws,
             st,
+            relay_hub,
             pb::ErrorCode::ErrorCodeProtocolViolation,
             "protocol_version mismatch",
             "msg-proto",
@@ -406,10 +794,12 @@ async fn handle_ws_message(
     }
 
     st.last_peer_seq = env.seq;
+    relay_hub.evict_acked(&st.session_id, env.ack).await;
+    st.in_flight.retain(|seq, _| *seq > env.ack);
     match env.payload {
         Some(pb::envelope::Payload::Telemetry(t)) => {
             if st.peer_role == pb::PeerRole::PeerRoleGameClient && st.is_primary_game {
-                relay_hub.publish_telemetry(&t);
+                relay_hub.publish_telemetry(&t).await;
             }
             info!(
                 agent_id = %st.agent_id.clone().unwrap_or_default(),
@@ -421,11 +811,14 @@ async fn handle_ws_message(
             );
         }
         Some(pb::envelope::Payload::Heartbeat(hb)) => {
+            let (outbound_buffered_bytes, outbound_dropped_frames) = st.outbound_stats();
             info!(
                 agent_id = %st.agent_id.clone().unwrap_or_default(),
                 rx = hb.rx_queue_len,
                 tx = hb.tx_queue_len,
                 drop_count = hb.dropped_frames,
+                outbound_buffered_bytes,
+                outbound_dropped_frames,
                 "heartbeat"
             );
         }
@@ -436,7 +829,7 @@ async fn handle_ws_message(
                 t1_mono_ms: now,
                 t2_mono_ms: now,
             };
-            send_envelope(ws, st, pb::envelope::Payload::TimeSyncRes(res)).await?;
+            send_envelope(ws, st, relay_hub, pb::envelope::Payload::TimeSyncRes(res), false).await?;
         }
         Some(pb::envelope::Payload::ActionReq(req)) => {
             if st.peer_role != pb::PeerRole::PeerRoleOrchestrator {
@@ -453,18 +846,18 @@ async fn handle_ws_message(
                     accepted: false,
                     reason: e.to_string(),
                 };
-                send_envelope(ws, st, pb::envelope::Payload::ActionAck(nack)).await?;
+                send_envelope(ws, st, relay_hub, pb::envelope::Payload::ActionAck(nack), true).await?;
             }
         }
         Some(pb::envelope::Payload::ActionAck(ack)) => {
-            if st.peer_role == pb::PeerRole::PeerRoleGameClient && st.is_primary_game {
+            if st.peer_role == pb::PeerRole::PeerRoleGameClient && st.agent_attached {
                 relay_hub.route_action_ack(&ack).await;
             } else {
                 warn!(session_id = %st.session_id, "unexpected action_ack");
             }
         }
         Some(pb::envelope::Payload::ActionRes(result)) => {
-            if st.peer_role == pb::PeerRole::PeerRoleGameClient && st.is_primary_game {
+            if st.peer_role == pb::PeerRole::PeerRoleGameClient && st.agent_attached {
                 relay_hub.route_action_result(&result).await;
             } else {
                 warn!(session_id = %st.session_id, "unexpected action_res");
@@ -484,25 +877,94 @@ async fn handle_ws_message(
                 "peer error"
             );
         }
+        Some(pb::envelope::Payload::Disconnect(d)) => {
+            info!(
+                session_id = %st.session_id,
+                reason = d.reason,
+                detail = %d.detail,
+                "peer disconnecting"
+            );
+            return Ok(false);
+        }
         None => {}
     }
     Ok(true)
 }
 
-fn decode_envelope(msg: WsMessage) -> Result<pb::Envelope> {
+/// Prefixes `buf` with a compression discriminator, compressing it first if
+/// `st` has negotiated compression and `buf` clears `compression_min_bytes`.
+///
+/// The discriminator byte only exists on the wire at all once this session
+/// has negotiated `CapCompressionV1` via `HelloAck` (`st.compression` is
+/// `Some`) — a peer that never advertised the capability (e.g. the hello
+/// exchange itself, or a client like the in-repo orchestrator that doesn't
+/// implement framing) gets raw, unprefixed protobuf, exactly as before
+/// compression support existed. `decode_envelope` mirrors this gating.
+fn frame_payload(st: &SessionState, buf: Vec<u8>) -> Vec<u8> {
+    let Some(kind) = st.compression else {
+        return buf;
+    };
+
+    if buf.len() >= st.compression_min_bytes {
+        match compress_payload(kind, &buf) {
+            Ok(compressed) => {
+                let mut framed = Vec::with_capacity(compressed.len() + 1);
+                framed.push(kind.discriminator());
+                framed.extend_from_slice(&compressed);
+                return framed;
+            }
+            Err(err) => {
+                warn!(error = %err, "envelope compression failed, sending raw");
+            }
+        }
+    }
+
+    let mut framed = Vec::with_capacity(buf.len() + 1);
+    framed.push(COMPRESSION_NONE);
+    framed.extend_from_slice(&buf);
+    framed
+}
+
+fn compress_payload(kind: CompressionKind, buf: &[u8]) -> Result<Vec<u8>> {
+    match kind {
+        CompressionKind::Zstd => zstd::stream::encode_all(buf, 0).context("zstd compress"),
+    }
+}
+
+/// Inverse of `frame_payload`: only expects a leading discriminator byte
+/// once `st` has itself negotiated compression, since that's the only case
+/// `frame_payload` ever writes one.
+fn decode_envelope(st: &SessionState, msg: WsMessage) -> Result<pb::Envelope> {
     let data = msg.into_data();
-    let env = pb::Envelope::decode(data.as_slice()).context("prost decode")?;
+    if st.compression.is_none() {
+        let env = pb::Envelope::decode(data.as_ref()).context("prost decode")?;
+        return Ok(env);
+    }
+
+    let (marker, body) = data.split_first().context("empty ws frame")?;
+    let decoded;
+    let raw: &[u8] = match *marker {
+        COMPRESSION_NONE => body,
+        COMPRESSION_ZSTD => {
+            decoded = zstd::stream::decode_all(body).context("zstd decompress")?;
+            &decoded
+        }
+        other => anyhow::bail!("unknown compression discriminator: {other}"),
+    };
+    let env = pb::Envelope::decode(raw).context("prost decode")?;
     Ok(env)
 }
 
 async fn send_handshake_ok(
     ws: &mut tokio_tungstenite::WebSocketStream<TlsStream<TcpStream>>,
     st: &mut SessionState,
+    relay_hub: &Arc<RelayHub>,
     use_hello_ack: bool,
     handshake_id: &str,
 ) -> Result<()> {
     if use_hello_ack {
         let negotiated = negotiated_capabilities(&st.peer_caps);
+        let compression_negotiated = negotiated.contains(&(pb::Capability::CapCompressionV1 as i32));
         let ack = pb::HelloAck {
             handshake_id: handshake_id.to_string(),
             accepted: true,
@@ -510,7 +972,13 @@ async fn send_handshake_ok(
             negotiated_capabilities: negotiated,
             server_version: "miqbot-bridge-server/0.3.0".to_string(),
         };
-        send_envelope(ws, st, pb::envelope::Payload::HelloAck(ack)).await
+        // Send the ack itself uncompressed, then flip on compression for
+        // everything after it — never the hello exchange.
+        send_envelope(ws, st, relay_hub, pb::envelope::Payload::HelloAck(ack), false).await?;
+        if compression_negotiated {
+            st.compression = Some(CompressionKind::Zstd);
+        }
+        Ok(())
     } else {
         let reply = pb::Hello {
             agent_id: "bridge".to_string(),
@@ -523,7 +991,7 @@ async fn send_handshake_ok(
             client_version: "miqbot-bridge-server/0.3.0".to_string(),
             handshake_id: handshake_id.to_string(),
         };
-        send_envelope(ws, st, pb::envelope::Payload::Hello(reply)).await
+        send_envelope(ws, st, relay_hub, pb::envelope::Payload::Hello(reply), false).await
     }
 }
 
@@ -538,6 +1006,7 @@ fn negotiated_capabilities(peer_caps: &[i32]) -> Vec<i32> {
 async fn send_handshake_reject(
     ws: &mut tokio_tungstenite::WebSocketStream<TlsStream<TcpStream>>,
     st: &mut SessionState,
+    relay_hub: &Arc<RelayHub>,
     use_hello_ack: bool,
     handshake_id: &str,
     reason: &str,
@@ -550,11 +1019,12 @@ async fn send_handshake_reject(
             negotiated_capabilities: Vec::new(),
             server_version: "miqbot-bridge-server/0.3.0".to_string(),
         };
-        send_envelope(ws, st, pb::envelope::Payload::HelloAck(ack)).await
+        send_envelope(ws, st, relay_hub, pb::envelope::Payload::HelloAck(ack), false).await
     } else {
         send_error(
             ws,
             st,
+            relay_hub,
             pb::ErrorCode::ErrorCodeUnauthorized,
             reason,
             "hello-reject",
@@ -566,6 +1036,7 @@ async fn send_handshake_reject(
 async fn send_error(
     ws: &mut tokio_tungstenite::WebSocketStream<TlsStream<TcpStream>>,
     st: &mut SessionState,
+    relay_hub: &Arc<RelayHub>,
     code: pb::ErrorCode,
     message: &str,
     correlation_hint: &str,
@@ -575,13 +1046,40 @@ async fn send_error(
         message: message.to_string(),
         correlation_id: format!("{}-{}", correlation_hint, Uuid::new_v4()),
     };
-    send_envelope(ws, st, pb::envelope::Payload::Error(err)).await
+    send_envelope(ws, st, relay_hub, pb::envelope::Payload::Error(err), false).await
+}
+
+/// Sends a `Disconnect` envelope carrying a machine-readable `reason`
+/// immediately before closing the underlying WebSocket, mirroring devp2p's
+/// pre-close `Disconnect` message so clients can pick a reconnect backoff
+/// suited to *why* they were dropped (e.g. back off hard on `LimitReached`,
+/// retry soon on `ServerShutdown`) instead of treating every close alike.
+async fn send_disconnect(
+    ws: &mut tokio_tungstenite::WebSocketStream<TlsStream<TcpStream>>,
+    st: &mut SessionState,
+    relay_hub: &Arc<RelayHub>,
+    reason: pb::DisconnectReason,
+    detail: &str,
+) -> Result<()> {
+    let frame = pb::Disconnect {
+        reason: reason as i32,
+        detail: detail.to_string(),
+    };
+    send_envelope(ws, st, relay_hub, pb::envelope::Payload::Disconnect(frame), false).await?;
+    let _ = ws.close(None).await;
+    Ok(())
 }
 
+/// Sends `payload` as a fresh `Envelope`. When `reliable` is set (actions and
+/// acks — never telemetry/heartbeats/handshake frames), the envelope is also
+/// tracked in `st.in_flight` until the peer's `ack` catches up to its `seq`,
+/// so `retry_in_flight` can resend it if that never happens.
 async fn send_envelope(
     ws: &mut tokio_tungstenite::WebSocketStream<TlsStream<TcpStream>>,
     st: &mut SessionState,
+    relay_hub: &Arc<RelayHub>,
     payload: pb::envelope::Payload,
+    reliable: bool,
 ) -> Result<()> {
     st.server_seq += 1;
     let env = pb::Envelope {
@@ -593,12 +1091,25 @@ async fn send_envelope(
         wall_unix_ms: wall_unix_ms(),
         payload: Some(payload),
     };
+    relay_hub.record_outbound(&st.session_id, &env).await;
+    if reliable {
+        st.in_flight.insert(
+            env.seq,
+            InFlightFrame {
+                env: env.clone(),
+                attempts: 0,
+                last_sent: Instant::now(),
+            },
+        );
+    }
 
     let mut buf = Vec::with_capacity(env.encoded_len());
     env.encode(&mut buf).context("encode env")?;
+    let framed = frame_payload(st, buf);
+
     match tokio::time::timeout(
         std::time::Duration::from_millis(st.send_timeout_ms),
-        ws.send(WsMessage::Binary(buf)),
+        ws.send(WsMessage::Binary(framed)),
     )
     .await
     {
@@ -612,6 +1123,54 @@ async fn send_envelope(
     Ok(())
 }
 
+/// Stages a `Telemetry` envelope in `st.outbound` (dropping stale queued
+/// telemetry first if the session is falling behind) and immediately
+/// attempts to flush it out over `ws`.
+async fn queue_telemetry(
+    ws: &mut tokio_tungstenite::WebSocketStream<TlsStream<TcpStream>>,
+    st: &mut SessionState,
+    relay_hub: &Arc<RelayHub>,
+    telemetry: pb::TelemetryFrame,
+) -> Result<()> {
+    st.server_seq += 1;
+    let env = pb::Envelope {
+        protocol_version: PROTOCOL_VERSION,
+        session_id: st.session_id.clone(),
+        seq: st.server_seq,
+        ack: st.last_peer_seq,
+        mono_ms: mono_ms(),
+        wall_unix_ms: wall_unix_ms(),
+        payload: Some(pb::envelope::Payload::Telemetry(telemetry)),
+    };
+    relay_hub.record_outbound(&st.session_id, &env).await;
+    st.outbound.enqueue(&env)?;
+    flush_outbound(ws, st).await
+}
+
+/// Sends every frame currently staged in `st.outbound`, oldest first.
+async fn flush_outbound(
+    ws: &mut tokio_tungstenite::WebSocketStream<TlsStream<TcpStream>>,
+    st: &mut SessionState,
+) -> Result<()> {
+    while let Some(buf) = st.outbound.pop() {
+        let framed = frame_payload(st, buf);
+        match tokio::time::timeout(
+            std::time::Duration::from_millis(st.send_timeout_ms),
+            ws.send(WsMessage::Binary(framed)),
+        )
+        .await
+        {
+            Ok(send_result) => {
+                send_result.context("ws send (outbound queue)")?;
+            }
+            Err(_) => {
+                anyhow::bail!("ws send timeout (outbound queue) after {}ms", st.send_timeout_ms);
+            }
+        }
+    }
+    Ok(())
+}
+
 fn mono_ms() -> u64 {
     use std::sync::OnceLock;
     static T0: OnceLock<std::time::Instant> = OnceLock::new();
@@ -1,71 +1,481 @@
+use crate::tts_backend::UtteranceId;
 use anyhow::{Context, Result};
-use std::path::{Path, PathBuf};
-use std::process::Command;
-use uuid::Uuid;
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::{FromSample, SizedSample, Stream, StreamConfig};
+use std::collections::{HashMap, VecDeque};
+use std::io::Cursor;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::oneshot;
+use tracing::warn;
+
+/// Why an utterance's `on_end` callback fired.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlaybackStatus {
+    Completed,
+    /// Cut short by `AudioPlayer::stop_all` (StopAll barge-in).
+    Cancelled,
+}
+
+/// Ring buffer headroom: a few seconds at a typical output rate, so a feed
+/// slightly ahead of the callback never has to block on the audio thread.
+const RING_CAPACITY_SAMPLES: usize = 48_000 * 2 * 4;
+
+/// One queued utterance's remaining sample count within the ring buffer, so
+/// the cpal callback can tell when playback crosses from one utterance into
+/// the next and fire lifecycle callbacks at the right sample boundary.
+struct UtteranceMarker {
+    id: UtteranceId,
+    remaining_samples: usize,
+    started: bool,
+    /// True while a streaming marker is still waiting on its first
+    /// `feed_streaming_chunk` call (see `begin_streaming`). The cpal callback
+    /// won't treat `remaining_samples == 0` as "finished" for an open marker,
+    /// so a streaming utterance registered before any samples arrive isn't
+    /// auto-completed out from under its own feeder.
+    open: bool,
+}
+
+struct PlaybackQueue {
+    ring: VecDeque<f32>,
+    markers: VecDeque<UtteranceMarker>,
+}
+
+type LifecycleCallback = Box<dyn Fn(&UtteranceId) + Send + Sync>;
+type EndCallback = Box<dyn Fn(&UtteranceId, PlaybackStatus) + Send + Sync>;
+
+#[derive(Default)]
+struct CallbackRegistry {
+    on_start: Mutex<Vec<LifecycleCallback>>,
+    on_end: Mutex<Vec<EndCallback>>,
+    waiters: Mutex<HashMap<UtteranceId, Vec<oneshot::Sender<()>>>>,
+}
+
+impl CallbackRegistry {
+    fn fire_start(&self, id: &UtteranceId) {
+        for cb in self.on_start.lock().expect("on_start callbacks poisoned").iter() {
+            cb(id);
+        }
+    }
+
+    fn fire_end(&self, id: &UtteranceId, status: PlaybackStatus) {
+        for cb in self.on_end.lock().expect("on_end callbacks poisoned").iter() {
+            cb(id, status);
+        }
+        if let Some(waiters) = self.waiters.lock().expect("waiters poisoned").remove(id) {
+            for tx in waiters {
+                let _ = tx.send(());
+            }
+        }
+    }
+}
 
 pub struct AudioPlayer {
     output_dir: PathBuf,
     fallback_wav_path: PathBuf,
+    queue: Arc<Mutex<PlaybackQueue>>,
+    callbacks: Arc<CallbackRegistry>,
+    device_channels: u16,
+    device_sample_rate: u32,
+    /// Set by `stop_all` and consumed by streaming feeders (e.g.
+    /// `pipeline::run_streaming`) to abort mid-utterance synthesis.
+    cancel_flag: Arc<AtomicBool>,
+    // Kept alive for the life of the player; dropping it tears down the stream.
+    _stream: Option<Stream>,
 }
 
 impl AudioPlayer {
-    pub fn new(output_dir: impl Into<PathBuf>, fallback_wav_path: impl Into<PathBuf>) -> Result<Self> {
+    pub fn new(
+        output_dir: impl Into<PathBuf>,
+        fallback_wav_path: impl Into<PathBuf>,
+        audio_realtime: bool,
+    ) -> Result<Self> {
         let output_dir = output_dir.into();
         std::fs::create_dir_all(&output_dir).context("create audio output_dir")?;
 
+        let queue = Arc::new(Mutex::new(PlaybackQueue {
+            ring: VecDeque::with_capacity(RING_CAPACITY_SAMPLES),
+            markers: VecDeque::new(),
+        }));
+        let callbacks = Arc::new(CallbackRegistry::default());
+
+        let (stream, device_channels, device_sample_rate) =
+            match open_output_stream(queue.clone(), callbacks.clone(), audio_realtime) {
+                Ok(opened) => opened,
+                Err(err) => {
+                    warn!(error = %err, "no cpal output device available, falling back to file-only playback");
+                    (None, 0, 0)
+                }
+            };
+
         Ok(Self {
             output_dir,
             fallback_wav_path: fallback_wav_path.into(),
+            queue,
+            callbacks,
+            device_channels,
+            device_sample_rate,
+            cancel_flag: Arc::new(AtomicBool::new(false)),
+            _stream: stream,
         })
     }
 
-    pub fn play_or_fallback(&self, wav_bytes: &[u8]) -> Result<PathBuf> {
-        let utterance_id = Uuid::new_v4().to_string();
-        let wav_path = self.output_dir.join(format!("{utterance_id}.wav"));
+    /// Register a handler invoked (on the cpal callback thread) when an
+    /// utterance's first sample is handed to the output device.
+    pub fn on_start(&self, cb: impl Fn(&UtteranceId) + Send + Sync + 'static) {
+        self.callbacks.on_start.lock().expect("on_start callbacks poisoned").push(Box::new(cb));
+    }
+
+    /// Register a handler invoked (on the cpal callback thread) when an
+    /// utterance's last sample has drained from the ring buffer, or it was
+    /// cut short by `stop_all`.
+    pub fn on_end(&self, cb: impl Fn(&UtteranceId, PlaybackStatus) + Send + Sync + 'static) {
+        self.callbacks.on_end.lock().expect("on_end callbacks poisoned").push(Box::new(cb));
+    }
+
+    /// Barge-in: immediately flushes any queued/playing audio and aborts a
+    /// streaming synthesis feeder's next chunk (see `take_cancelled`). Every
+    /// utterance still tracked in the ring buffer fires `on_end` with
+    /// `PlaybackStatus::Cancelled` before this returns.
+    pub fn stop_all(&self) {
+        self.cancel_flag.store(true, Ordering::SeqCst);
+
+        let cancelled: Vec<UtteranceId> = {
+            let mut queue = self.queue.lock().expect("queue poisoned");
+            queue.ring.clear();
+            queue.markers.drain(..).map(|marker| marker.id).collect()
+        };
+        for id in &cancelled {
+            self.callbacks.fire_end(id, PlaybackStatus::Cancelled);
+        }
+    }
+
+    /// Consumes (and clears) the barge-in flag raised by `stop_all`. A
+    /// streaming feeder should call this between chunks and stop issuing
+    /// further synthesis once it returns `true`.
+    pub fn take_cancelled(&self) -> bool {
+        self.cancel_flag.swap(false, Ordering::SeqCst)
+    }
+
+    /// Resolves once `utterance_id` has finished playing (or immediately if
+    /// it is not currently tracked, e.g. it already finished or playback
+    /// fell back to file-only mode).
+    ///
+    /// Locks `queue` before (nested around) `waiters`, matching the cpal
+    /// callback's queue-then-waiters order, and holds `queue` across the
+    /// whole still-queued check and waiter registration so the callback
+    /// can't remove the marker and fire `fire_end` in between — which would
+    /// otherwise let this call register a waiter that never gets notified.
+    pub async fn await_playback_done(&self, utterance_id: UtteranceId) {
+        let rx = {
+            let queue = self.queue.lock().expect("queue poisoned");
+            let still_queued = queue.markers.iter().any(|m| m.id == utterance_id);
+            if !still_queued {
+                return;
+            }
+            let mut waiters = self.callbacks.waiters.lock().expect("waiters poisoned");
+            let (tx, rx) = oneshot::channel();
+            waiters.entry(utterance_id).or_default().push(tx);
+            rx
+        };
+        let _ = rx.await;
+    }
+
+    pub fn play_or_fallback(&self, wav_bytes: &[u8]) -> Result<(PathBuf, UtteranceId)> {
+        let utterance_id = UtteranceId::new();
+        let wav_path = self.output_dir.join(format!("{}.wav", utterance_id.0));
         std::fs::write(&wav_path, wav_bytes).with_context(|| format!("write wav: {}", wav_path.display()))?;
 
-        if self.try_play(&wav_path).is_ok() {
-            return Ok(wav_path);
+        if self._stream.is_some() {
+            match self.feed_ring_buffer(wav_bytes, utterance_id.clone()) {
+                Ok(()) => return Ok((wav_path, utterance_id)),
+                Err(err) => warn!(error = %err, "cpal feed failed, falling back to wav file"),
+            }
         }
 
         std::fs::write(&self.fallback_wav_path, wav_bytes)
             .with_context(|| format!("write fallback wav: {}", self.fallback_wav_path.display()))?;
-        Ok(self.fallback_wav_path.clone())
-    }
-
-    fn try_play(&self, wav_path: &Path) -> Result<()> {
-        #[cfg(target_os = "windows")]
-        {
-            let escaped = wav_path.display().to_string().replace("'", "''");
-            let cmd = format!("(New-Object Media.SoundPlayer '{escaped}').Play()");
-            Command::new("powershell")
-                .args(["-NoProfile", "-Command", &cmd])
-                .spawn()
-                .context("spawn windows sound player")?;
-            return Ok(());
+        // No ring playback to report lifecycle for; fire start/end back to back.
+        self.callbacks.fire_start(&utterance_id);
+        self.callbacks.fire_end(&utterance_id, PlaybackStatus::Completed);
+        Ok((self.fallback_wav_path.clone(), utterance_id))
+    }
+
+    /// Registers a new utterance with zero samples queued, for a streaming
+    /// synth whose total length isn't known up front. Follow up with
+    /// `feed_streaming_chunk` for each PCM chunk as it arrives; the marker's
+    /// `remaining_samples` grows with each call and drains normally once the
+    /// caller stops feeding it, so playback lifecycle works exactly like the
+    /// whole-clip path in `play_or_fallback`.
+    pub fn begin_streaming(&self) -> UtteranceId {
+        let utterance_id = UtteranceId::new();
+        if self._stream.is_some() {
+            let mut queue = self.queue.lock().expect("queue poisoned");
+            queue.markers.push_back(UtteranceMarker {
+                id: utterance_id.clone(),
+                remaining_samples: 0,
+                started: false,
+                open: true,
+            });
         }
+        utterance_id
+    }
 
-        #[cfg(target_os = "macos")]
-        {
-            Command::new("afplay")
-                .arg(wav_path)
-                .spawn()
-                .context("spawn afplay")?;
-            return Ok(());
+    /// Remixes/resamples one chunk of raw PCM `samples` and appends it to
+    /// the ring buffer under `utterance_id`'s marker. A no-op if there is no
+    /// cpal output device (the caller still gets lifecycle via
+    /// `await_playback_done` returning immediately for an untracked id).
+    pub fn feed_streaming_chunk(&self, utterance_id: &UtteranceId, samples: &[f32], src_channels: u16, src_sample_rate: u32) {
+        if self._stream.is_none() || samples.is_empty() {
+            return;
         }
 
-        #[cfg(target_os = "linux")]
-        {
-            if Command::new("aplay").arg(wav_path).spawn().is_ok() {
-                return Ok(());
-            }
-            if Command::new("paplay").arg(wav_path).spawn().is_ok() {
-                return Ok(());
+        let remixed = remix_channels(samples, src_channels, self.device_channels);
+        let resampled = resample_linear(&remixed, src_sample_rate, self.device_sample_rate, self.device_channels);
+        if resampled.is_empty() {
+            return;
+        }
+
+        let mut queue = self.queue.lock().expect("queue poisoned");
+        if let Some(marker) = queue.markers.iter_mut().find(|m| m.id == *utterance_id) {
+            marker.remaining_samples += resampled.len();
+            marker.open = false;
+        }
+        queue.ring.extend(resampled);
+    }
+
+    fn feed_ring_buffer(&self, wav_bytes: &[u8], utterance_id: UtteranceId) -> Result<()> {
+        let mut reader = hound::WavReader::new(Cursor::new(wav_bytes)).context("parse wav header")?;
+        let spec = reader.spec();
+
+        let source: Vec<f32> = match spec.sample_format {
+            hound::SampleFormat::Float => reader
+                .samples::<f32>()
+                .collect::<std::result::Result<Vec<_>, _>>()
+                .context("decode float wav samples")?,
+            hound::SampleFormat::Int => {
+                let full_scale = (1_i64 << (spec.bits_per_sample - 1)) as f32;
+                reader
+                    .samples::<i32>()
+                    .map(|s| s.map(|v| v as f32 / full_scale))
+                    .collect::<std::result::Result<Vec<_>, _>>()
+                    .context("decode int wav samples")?
             }
-            anyhow::bail!("no supported linux audio player command found (aplay/paplay)");
+        };
+
+        let remixed = remix_channels(&source, spec.channels, self.device_channels);
+        let resampled = resample_linear(&remixed, spec.sample_rate, self.device_sample_rate, self.device_channels);
+
+        let mut queue = self.queue.lock().expect("queue poisoned");
+        if resampled.is_empty() {
+            drop(queue);
+            self.callbacks.fire_start(&utterance_id);
+            self.callbacks.fire_end(&utterance_id, PlaybackStatus::Completed);
+            return Ok(());
+        }
+
+        queue.markers.push_back(UtteranceMarker {
+            id: utterance_id,
+            remaining_samples: resampled.len(),
+            started: false,
+            open: false,
+        });
+        queue.ring.extend(resampled);
+        Ok(())
+    }
+}
+
+fn open_output_stream(
+    queue: Arc<Mutex<PlaybackQueue>>,
+    callbacks: Arc<CallbackRegistry>,
+    audio_realtime: bool,
+) -> Result<(Option<Stream>, u16, u32)> {
+    let host = cpal::default_host();
+    let device = host.default_output_device().context("no default output device")?;
+    let config = device.default_output_config().context("no default output config")?;
+
+    let channels = config.channels();
+    let sample_rate = config.sample_rate().0;
+    let sample_format = config.sample_format();
+    let buffer_frames = match config.buffer_size() {
+        cpal::SupportedBufferSize::Range { min, .. } => *min,
+        cpal::SupportedBufferSize::Unknown => 512,
+    };
+    let stream_config: StreamConfig = config.into();
+
+    let stream = match sample_format {
+        cpal::SampleFormat::F32 => {
+            build_stream::<f32>(&device, &stream_config, queue, callbacks, audio_realtime, buffer_frames, sample_rate)?
+        }
+        cpal::SampleFormat::I16 => {
+            build_stream::<i16>(&device, &stream_config, queue, callbacks, audio_realtime, buffer_frames, sample_rate)?
+        }
+        cpal::SampleFormat::U16 => {
+            build_stream::<u16>(&device, &stream_config, queue, callbacks, audio_realtime, buffer_frames, sample_rate)?
+        }
+        other => anyhow::bail!("unsupported cpal sample format: {other:?}"),
+    };
+    stream.play().context("start cpal output stream")?;
+
+    Ok((Some(stream), channels, sample_rate))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn build_stream<T>(
+    device: &cpal::Device,
+    config: &StreamConfig,
+    queue: Arc<Mutex<PlaybackQueue>>,
+    callbacks: Arc<CallbackRegistry>,
+    audio_realtime: bool,
+    buffer_frames: u32,
+    sample_rate: u32,
+) -> Result<Stream>
+where
+    T: SizedSample + FromSample<f32>,
+{
+    let promoted = std::sync::atomic::AtomicBool::new(false);
+
+    device
+        .build_output_stream(
+            config,
+            move |data: &mut [T], _| {
+                if audio_realtime && !promoted.swap(true, std::sync::atomic::Ordering::Relaxed) {
+                    // Must run on the thread actually executing this callback, so
+                    // this is deliberately done on first invocation rather than
+                    // right after `stream.play()` on the caller's thread. cpal
+                    // gives no teardown hook to demote symmetrically on stop.
+                    if let Err(err) =
+                        audio_thread_priority::promote_current_thread_to_real_time(buffer_frames, sample_rate)
+                    {
+                        warn!(error = %err, "failed to promote audio output thread to real-time priority");
+                    }
+                }
+
+                // Lock order is queue-then-waiters throughout this module (see
+                // `AudioPlayer::await_playback_done`); `fire_start`/`fire_end`
+                // lock `waiters`, so they must never be called while holding
+                // `queue`, or a waiter doing the reverse acquisition deadlocks
+                // this real-time callback thread. Collect ids under the queue
+                // lock, release it, then fire callbacks afterward.
+                let mut started_ids: Vec<UtteranceId> = Vec::new();
+                let mut finished_ids: Vec<UtteranceId> = Vec::new();
+                {
+                    let mut queue = queue.lock().expect("queue poisoned");
+                    for sample in data.iter_mut() {
+                        let started_id = queue.markers.front_mut().and_then(|marker| {
+                            if marker.started {
+                                None
+                            } else {
+                                marker.started = true;
+                                Some(marker.id.clone())
+                            }
+                        });
+                        if let Some(id) = started_id {
+                            started_ids.push(id);
+                        }
+
+                        let value = queue.ring.pop_front().unwrap_or(0.0);
+                        *sample = T::from_sample(value);
+
+                        let finished_id = queue.markers.front_mut().and_then(|marker| {
+                            marker.remaining_samples = marker.remaining_samples.saturating_sub(1);
+                            // A streaming marker with `open == true` hasn't had its
+                            // first chunk fed yet (see `begin_streaming`), so
+                            // `remaining_samples == 0` just means "nothing queued
+                            // yet", not "finished" — don't pop it out from under
+                            // `feed_streaming_chunk`.
+                            (!marker.open && marker.remaining_samples == 0).then(|| marker.id.clone())
+                        });
+                        if finished_id.is_some() {
+                            queue.markers.pop_front();
+                        }
+                        if let Some(id) = finished_id {
+                            finished_ids.push(id);
+                        }
+                    }
+                }
+                for id in &started_ids {
+                    callbacks.fire_start(id);
+                }
+                for id in &finished_ids {
+                    callbacks.fire_end(id, PlaybackStatus::Completed);
+                }
+            },
+            |err| warn!(error = %err, "cpal output stream error"),
+            None,
+        )
+        .context("build cpal output stream")
+}
+
+/// Decodes a raw (headerless) PCM chunk straight to `f32` samples in
+/// `[-1.0, 1.0]`, for streaming synth chunks that arrive after the WAV
+/// header has already been parsed once. Mirrors the int-sample path in
+/// `feed_ring_buffer`, just without `hound`'s need for a complete reader.
+pub fn decode_pcm_bytes(bytes: &[u8], bits_per_sample: u16) -> Vec<f32> {
+    match bits_per_sample {
+        8 => bytes.iter().map(|&b| (b as f32 - 128.0) / 128.0).collect(),
+        16 => bytes
+            .chunks_exact(2)
+            .map(|b| i16::from_le_bytes([b[0], b[1]]) as f32 / i16::MAX as f32)
+            .collect(),
+        32 => bytes
+            .chunks_exact(4)
+            .map(|b| i32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f32 / i32::MAX as f32)
+            .collect(),
+        other => {
+            warn!(bits_per_sample = other, "unsupported streaming PCM bit depth, dropping chunk");
+            Vec::new()
         }
+    }
+}
+
+fn remix_channels(samples: &[f32], src_channels: u16, dst_channels: u16) -> Vec<f32> {
+    if src_channels == dst_channels || src_channels == 0 || dst_channels == 0 {
+        return samples.to_vec();
+    }
 
-        #[allow(unreachable_code)]
-        anyhow::bail!("audio playback not implemented for this platform")
+    let src_channels = src_channels as usize;
+    let dst_channels = dst_channels as usize;
+    let frames = samples.len() / src_channels;
+    let mut out = Vec::with_capacity(frames * dst_channels);
+
+    for frame in samples.chunks_exact(src_channels) {
+        let mono: f32 = frame.iter().sum::<f32>() / src_channels as f32;
+        for _ in 0..dst_channels {
+            out.push(mono);
+        }
+    }
+    out
+}
+
+/// Simple linear resampler; good enough for speech-rate WAVs where a device's
+/// native rate differs from the TTS service's 48kHz output.
+fn resample_linear(samples: &[f32], src_rate: u32, dst_rate: u32, channels: u16) -> Vec<f32> {
+    if src_rate == dst_rate || src_rate == 0 || dst_rate == 0 || channels == 0 {
+        return samples.to_vec();
+    }
+
+    let channels = channels as usize;
+    let src_frames = samples.len() / channels;
+    if src_frames == 0 {
+        return Vec::new();
+    }
+
+    let dst_frames = (src_frames as u64 * dst_rate as u64 / src_rate as u64) as usize;
+    let mut out = Vec::with_capacity(dst_frames * channels);
+
+    for dst_frame in 0..dst_frames {
+        let src_pos = dst_frame as f64 * src_rate as f64 / dst_rate as f64;
+        let i0 = src_pos.floor() as usize;
+        let frac = (src_pos - i0 as f64) as f32;
+        let i1 = (i0 + 1).min(src_frames - 1);
+
+        for ch in 0..channels {
+            let a = samples[i0.min(src_frames - 1) * channels + ch];
+            let b = samples[i1 * channels + ch];
+            out.push(a + (b - a) * frac);
+        }
     }
+    out
 }
@@ -11,6 +11,8 @@ pub struct OrchestratorConfig {
     pub tts_url: String,
     pub subtitle_url: String,
     pub tts_mode: String,
+    #[serde(default = "default_tts_backend")]
+    pub tts_backend: String,
 
     pub silence_gap_ms: u64,
     pub state_tick_ms: u64,
@@ -28,6 +30,39 @@ pub struct OrchestratorConfig {
     pub audio_output_dir: String,
     pub fallback_wav_path: String,
     pub metrics_jsonl_path: String,
+    /// Address (e.g. `0.0.0.0:9464`) to serve a Prometheus `/metrics`
+    /// endpoint on, mirroring the same events written to
+    /// `metrics_jsonl_path`. Left unset, no HTTP server is started.
+    #[serde(default)]
+    pub metrics_http_addr: Option<String>,
+    #[serde(default = "default_audio_realtime")]
+    pub audio_realtime: bool,
+
+    /// Backoff before the first bridge reconnect attempt after the
+    /// connection drops.
+    #[serde(default = "default_reconnect_initial_backoff_ms")]
+    pub reconnect_initial_backoff_ms: u64,
+    /// Cap on the exponential bridge reconnect backoff.
+    #[serde(default = "default_reconnect_max_backoff_ms")]
+    pub reconnect_max_backoff_ms: u64,
+
+    /// Timeout applied to each individual subtitle-gateway request attempt.
+    #[serde(default = "default_subtitle_request_timeout_ms")]
+    pub subtitle_request_timeout_ms: u64,
+    /// Attempts a `post_subtitle` call makes before giving up, capped by
+    /// whichever of this or `subtitle_retry_deadline_ms` is hit first.
+    #[serde(default = "default_subtitle_max_attempts")]
+    pub subtitle_max_attempts: u32,
+    /// Backoff before the first subtitle retry.
+    #[serde(default = "default_subtitle_initial_backoff_ms")]
+    pub subtitle_initial_backoff_ms: u64,
+    /// Cap on the exponential subtitle retry backoff.
+    #[serde(default = "default_subtitle_max_backoff_ms")]
+    pub subtitle_max_backoff_ms: u64,
+    /// Wall-clock budget for a whole `post_subtitle` call, across all
+    /// attempts.
+    #[serde(default = "default_subtitle_retry_deadline_ms")]
+    pub subtitle_retry_deadline_ms: u64,
 
     pub tls: TlsConfig,
 }
@@ -36,6 +71,56 @@ pub struct OrchestratorConfig {
 pub enum TtsMode {
     WavOnly,
     WithMeta,
+    /// Consumes `/v1/tts_stream`'s chunked response incrementally instead
+    /// of buffering the whole clip; see `TtsClient::synthesize_streaming`.
+    Streaming,
+}
+
+/// Which `TtsBackend` implementation to construct. `Remote` talks to the
+/// microservice over HTTP; the native variants run fully offline using the
+/// host OS's own synthesizer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TtsBackendKind {
+    Remote,
+    WindowsSpeech,
+    SpeechDispatcher,
+    MacSay,
+}
+
+fn default_tts_backend() -> String {
+    "remote".to_string()
+}
+
+fn default_audio_realtime() -> bool {
+    true
+}
+
+fn default_reconnect_initial_backoff_ms() -> u64 {
+    250
+}
+
+fn default_reconnect_max_backoff_ms() -> u64 {
+    10_000
+}
+
+fn default_subtitle_request_timeout_ms() -> u64 {
+    2_000
+}
+
+fn default_subtitle_max_attempts() -> u32 {
+    3
+}
+
+fn default_subtitle_initial_backoff_ms() -> u64 {
+    200
+}
+
+fn default_subtitle_max_backoff_ms() -> u64 {
+    2_000
+}
+
+fn default_subtitle_retry_deadline_ms() -> u64 {
+    6_000
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -56,10 +141,20 @@ impl OrchestratorConfig {
     pub fn tts_mode(&self) -> TtsMode {
         match self.tts_mode.as_str() {
             "with_meta" => TtsMode::WithMeta,
+            "streaming" => TtsMode::Streaming,
             _ => TtsMode::WavOnly,
         }
     }
 
+    pub fn tts_backend_kind(&self) -> TtsBackendKind {
+        match self.tts_backend.as_str() {
+            "windows_speech" => TtsBackendKind::WindowsSpeech,
+            "speech_dispatcher" => TtsBackendKind::SpeechDispatcher,
+            "mac_say" => TtsBackendKind::MacSay,
+            _ => TtsBackendKind::Remote,
+        }
+    }
+
     pub fn validate(&self) -> Result<()> {
         if self.bridge_url.trim().is_empty() {
             bail!("bridge_url must not be empty");
@@ -103,6 +198,35 @@ impl OrchestratorConfig {
         if self.metrics_jsonl_path.trim().is_empty() {
             bail!("metrics_jsonl_path must not be empty");
         }
+        if let Some(addr) = &self.metrics_http_addr {
+            if addr.trim().is_empty() {
+                bail!("metrics_http_addr must not be empty when set");
+            }
+            if addr.parse::<std::net::SocketAddr>().is_err() {
+                bail!("metrics_http_addr must be a valid socket address (e.g. 0.0.0.0:9464)");
+            }
+        }
+        if self.reconnect_initial_backoff_ms == 0 {
+            bail!("reconnect_initial_backoff_ms must be > 0");
+        }
+        if self.reconnect_max_backoff_ms < self.reconnect_initial_backoff_ms {
+            bail!("reconnect_max_backoff_ms must be >= reconnect_initial_backoff_ms");
+        }
+        if self.subtitle_request_timeout_ms == 0 {
+            bail!("subtitle_request_timeout_ms must be > 0");
+        }
+        if self.subtitle_max_attempts == 0 {
+            bail!("subtitle_max_attempts must be > 0");
+        }
+        if self.subtitle_initial_backoff_ms == 0 {
+            bail!("subtitle_initial_backoff_ms must be > 0");
+        }
+        if self.subtitle_max_backoff_ms < self.subtitle_initial_backoff_ms {
+            bail!("subtitle_max_backoff_ms must be >= subtitle_initial_backoff_ms");
+        }
+        if self.subtitle_retry_deadline_ms < self.subtitle_request_timeout_ms {
+            bail!("subtitle_retry_deadline_ms must be >= subtitle_request_timeout_ms");
+        }
         Ok(())
     }
 }
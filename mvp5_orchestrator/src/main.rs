@@ -4,6 +4,7 @@ mod config;
 mod pb;
 mod speech_policy;
 mod subtitle_client;
+mod tts_backend;
 mod tts_client;
 
 use anyhow::{Context, Result};
@@ -14,8 +15,9 @@ use speech_policy::SpeechPolicy;
 use std::fs::OpenOptions;
 use std::io::Write;
 use std::time::Instant;
-use subtitle_client::SubtitleClient;
+use subtitle_client::{SubtitleClient, SubtitleRetryConfig};
 use tracing::{info, warn};
+use tts_backend::TtsBackend;
 use tts_client::TtsClient;
 
 #[tokio::main]
@@ -30,9 +32,25 @@ async fn main() -> Result<()> {
         .await
         .context("connect bridge")?;
 
-    let tts = TtsClient::new(cfg.tts_url.clone());
-    let subtitle = SubtitleClient::new(cfg.subtitle_url.clone());
-    let audio = AudioPlayer::new(cfg.audio_output_dir.clone(), cfg.fallback_wav_path.clone())?;
+    let tts = tts_backend::build_backend(
+        cfg.tts_backend_kind(),
+        TtsClient::new(cfg.tts_url.clone(), cfg.tts_mode()),
+    );
+    let subtitle = SubtitleClient::with_retry_config(
+        cfg.subtitle_url.clone(),
+        SubtitleRetryConfig {
+            request_timeout_ms: cfg.subtitle_request_timeout_ms,
+            max_attempts: cfg.subtitle_max_attempts,
+            initial_backoff_ms: cfg.subtitle_initial_backoff_ms,
+            max_backoff_ms: cfg.subtitle_max_backoff_ms,
+            retry_deadline_ms: cfg.subtitle_retry_deadline_ms,
+        },
+    );
+    let audio = AudioPlayer::new(
+        cfg.audio_output_dir.clone(),
+        cfg.fallback_wav_path.clone(),
+        cfg.audio_realtime,
+    )?;
 
     let mut speech_policy = SpeechPolicy::new(cfg.silence_gap_ms, cfg.duplicate_cooldown_ms);
     let t0 = Instant::now();
@@ -92,7 +110,7 @@ async fn main() -> Result<()> {
 
 async fn process_speech(
     cfg: &OrchestratorConfig,
-    tts: &TtsClient,
+    tts: &dyn TtsBackend,
     subtitle: &SubtitleClient,
     audio: &AudioPlayer,
     text: &str,
@@ -107,10 +125,10 @@ async fn process_speech(
     }
 
     let tts_started = Instant::now();
-    let wav = tts.synthesize(text).await?;
-    let ttft_ms = tts_started.elapsed().as_millis() as u64;
+    let synth = tts.synthesize(text).await?;
+    let ttft_ms = synth.ttft_ms.unwrap_or_else(|| tts_started.elapsed().as_millis() as u64);
 
-    let output_path = audio.play_or_fallback(&wav)?;
+    let (output_path, _utterance_id) = audio.play_or_fallback(&synth.wav_bytes)?;
     let pipeline_latency_ms = started.elapsed().as_millis() as u64;
     let silence_gap_ms = now_ms.saturating_sub(last_spoken_ms);
 
@@ -0,0 +1,247 @@
+//! In-memory mirror of the same events `StateActor::append_metric_line`
+//! writes to the JSONL sink, rendered as Prometheus text exposition format
+//! on demand. Kept as a second, independent sink rather than a replacement
+//! so the JSONL file (used for offline analysis) and the live `/metrics`
+//! scrape target (used for dashboards/alerting) can't drift apart: both are
+//! fed from the same call site.
+
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpListener;
+use tracing::{info, warn};
+
+/// Upper bounds (inclusive, milliseconds) of the histogram buckets shared by
+/// every latency metric below. A `+Inf` bucket is always appended on top of
+/// these at render time.
+const LATENCY_BUCKETS_MS: &[u64] = &[50, 100, 200, 500, 1_000, 2_000, 5_000, 10_000];
+
+#[derive(Default)]
+struct Histogram {
+    /// `bucket_hits[i]` counts observations that landed in
+    /// `(LATENCY_BUCKETS_MS[i - 1], LATENCY_BUCKETS_MS[i]]` (or `<=
+    /// LATENCY_BUCKETS_MS[0]` for `i == 0`); the last slot is the `+Inf`
+    /// overflow bucket. Rendered as Prometheus's usual cumulative counts.
+    bucket_hits: Vec<AtomicU64>,
+    sum_ms: AtomicU64,
+    count: AtomicU64,
+}
+
+impl Histogram {
+    fn new() -> Self {
+        Self {
+            bucket_hits: (0..=LATENCY_BUCKETS_MS.len()).map(|_| AtomicU64::new(0)).collect(),
+            sum_ms: AtomicU64::new(0),
+            count: AtomicU64::new(0),
+        }
+    }
+
+    fn observe(&self, value_ms: u64) {
+        let idx = LATENCY_BUCKETS_MS
+            .iter()
+            .position(|&bound| value_ms <= bound)
+            .unwrap_or(LATENCY_BUCKETS_MS.len());
+        self.bucket_hits[idx].fetch_add(1, Ordering::Relaxed);
+        self.sum_ms.fetch_add(value_ms, Ordering::Relaxed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn render(&self, name: &str, out: &mut String) {
+        let _ = writeln!(out, "# TYPE {name} histogram");
+        let mut cumulative = 0_u64;
+        for (i, bound) in LATENCY_BUCKETS_MS.iter().enumerate() {
+            cumulative += self.bucket_hits[i].load(Ordering::Relaxed);
+            let _ = writeln!(out, "{name}_bucket{{le=\"{bound}\"}} {cumulative}");
+        }
+        cumulative += self.bucket_hits[LATENCY_BUCKETS_MS.len()].load(Ordering::Relaxed);
+        let _ = writeln!(out, "{name}_bucket{{le=\"+Inf\"}} {cumulative}");
+        let _ = writeln!(out, "{name}_sum {}", self.sum_ms.load(Ordering::Relaxed));
+        let _ = writeln!(out, "{name}_count {}", self.count.load(Ordering::Relaxed));
+    }
+}
+
+/// Live metrics backing the `/metrics` endpoint. Cheap enough to update from
+/// the hot path (a handful of atomics and a couple of small, short-held
+/// mutexes), independent of how often (if ever) it's actually scraped.
+pub struct MetricsRegistry {
+    speech_dropped: Mutex<HashMap<(String, String, &'static str), u64>>,
+    action_timeout: Mutex<HashMap<&'static str, u64>>,
+    ttft_ms: Histogram,
+    tts_total_ms: Histogram,
+    pipeline_latency_ms: Histogram,
+    queue_wait_ms: Histogram,
+    queue_depth_p0: AtomicU64,
+    queue_depth_p1: AtomicU64,
+    queue_depth_p2: AtomicU64,
+}
+
+impl MetricsRegistry {
+    pub fn new() -> Self {
+        Self {
+            speech_dropped: Mutex::new(HashMap::new()),
+            action_timeout: Mutex::new(HashMap::new()),
+            ttft_ms: Histogram::new(),
+            tts_total_ms: Histogram::new(),
+            pipeline_latency_ms: Histogram::new(),
+            queue_wait_ms: Histogram::new(),
+            queue_depth_p0: AtomicU64::new(0),
+            queue_depth_p1: AtomicU64::new(0),
+            queue_depth_p2: AtomicU64::new(0),
+        }
+    }
+
+    /// Folds one JSONL metric event (the same `serde_json::Value` that was
+    /// just appended to `metrics_jsonl_path`) into the in-memory counters
+    /// and histograms. Unrecognized `event` values are ignored, so adding a
+    /// new JSONL event type elsewhere doesn't require touching this match.
+    pub fn record_event(&self, value: &serde_json::Value) {
+        match value.get("event").and_then(|v| v.as_str()) {
+            Some("speech_dropped") => {
+                let priority = value.get("priority").and_then(|v| v.as_str()).unwrap_or("unknown");
+                let source = value.get("source").and_then(|v| v.as_str()).unwrap_or("unknown");
+                let reason = match value.get("dropped_reason").and_then(|v| v.as_str()) {
+                    Some("queue_overflow") => "queue_overflow",
+                    _ => "deadline_expired",
+                };
+                let key = (priority.to_string(), source.to_string(), reason);
+                *self.speech_dropped.lock().expect("metrics lock poisoned").entry(key).or_insert(0) += 1;
+            }
+            Some("action_timeout") => {
+                let kind = match value.get("kind").and_then(|v| v.as_str()) {
+                    Some("result") => "result",
+                    _ => "ack",
+                };
+                *self.action_timeout.lock().expect("metrics lock poisoned").entry(kind).or_insert(0) += 1;
+            }
+            Some("speech_pipeline") => {
+                if let Some(v) = value.get("ttft_ms").and_then(|v| v.as_u64()) {
+                    self.ttft_ms.observe(v);
+                }
+                if let Some(v) = value.get("tts_total_ms").and_then(|v| v.as_u64()) {
+                    self.tts_total_ms.observe(v);
+                }
+                if let Some(v) = value.get("pipeline_latency_ms").and_then(|v| v.as_u64()) {
+                    self.pipeline_latency_ms.observe(v);
+                }
+                if let Some(v) = value.get("queue_wait_ms").and_then(|v| v.as_u64()) {
+                    self.queue_wait_ms.observe(v);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    pub fn set_queue_depths(&self, p0: usize, p1: usize, p2: usize) {
+        self.queue_depth_p0.store(p0 as u64, Ordering::Relaxed);
+        self.queue_depth_p1.store(p1 as u64, Ordering::Relaxed);
+        self.queue_depth_p2.store(p2 as u64, Ordering::Relaxed);
+    }
+
+    fn render(&self) -> String {
+        let mut out = String::new();
+
+        let _ = writeln!(out, "# TYPE speech_dropped_total counter");
+        for ((priority, source, reason), count) in
+            self.speech_dropped.lock().expect("metrics lock poisoned").iter()
+        {
+            let _ = writeln!(
+                out,
+                "speech_dropped_total{{priority=\"{priority}\",source=\"{source}\",reason=\"{reason}\"}} {count}"
+            );
+        }
+
+        let _ = writeln!(out, "# TYPE action_timeout_total counter");
+        for (kind, count) in self.action_timeout.lock().expect("metrics lock poisoned").iter() {
+            let _ = writeln!(out, "action_timeout_total{{kind=\"{kind}\"}} {count}");
+        }
+
+        self.ttft_ms.render("ttft_ms", &mut out);
+        self.tts_total_ms.render("tts_total_ms", &mut out);
+        self.pipeline_latency_ms.render("pipeline_latency_ms", &mut out);
+        self.queue_wait_ms.render("queue_wait_ms", &mut out);
+
+        let _ = writeln!(out, "# TYPE speech_queue_depth gauge");
+        let _ = writeln!(
+            out,
+            "speech_queue_depth{{priority=\"p0_safety\"}} {}",
+            self.queue_depth_p0.load(Ordering::Relaxed)
+        );
+        let _ = writeln!(
+            out,
+            "speech_queue_depth{{priority=\"p1_chat_reply\"}} {}",
+            self.queue_depth_p1.load(Ordering::Relaxed)
+        );
+        let _ = writeln!(
+            out,
+            "speech_queue_depth{{priority=\"p2_commentary\"}} {}",
+            self.queue_depth_p2.load(Ordering::Relaxed)
+        );
+
+        out
+    }
+}
+
+impl Default for MetricsRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Serves `GET /metrics` in Prometheus text exposition format on `addr`
+/// until the process exits. Anything other than `GET /metrics` gets a bare
+/// 404; this isn't a general-purpose HTTP server, just enough of one for a
+/// scrape target.
+pub async fn serve(addr: String, registry: Arc<MetricsRegistry>) -> Result<()> {
+    let listener = TcpListener::bind(&addr)
+        .await
+        .with_context(|| format!("bind metrics_http_addr {addr}"))?;
+    info!(addr = %addr, "metrics http server listening");
+
+    loop {
+        let (stream, _) = listener.accept().await.context("accept metrics connection")?;
+        let registry = Arc::clone(&registry);
+        tokio::spawn(async move {
+            if let Err(err) = handle_connection(stream, &registry).await {
+                warn!(error = %err, "metrics connection error");
+            }
+        });
+    }
+}
+
+async fn handle_connection(stream: tokio::net::TcpStream, registry: &MetricsRegistry) -> Result<()> {
+    let mut reader = BufReader::new(stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line).await.context("read request line")?;
+
+    // Drain and discard headers; we don't need any of them.
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).await.context("read header line")? == 0 || line == "\r\n" {
+            break;
+        }
+    }
+
+    let stream = reader.get_mut();
+    if request_line.starts_with("GET /metrics ") {
+        let body = registry.render();
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        );
+        stream.write_all(response.as_bytes()).await.context("write metrics response")?;
+    } else {
+        let body = "not found";
+        let response = format!(
+            "HTTP/1.1 404 Not Found\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        );
+        stream.write_all(response.as_bytes()).await.context("write 404 response")?;
+    }
+    stream.flush().await.context("flush metrics response")?;
+    Ok(())
+}
@@ -1,11 +1,13 @@
-use crate::audio_player::AudioPlayer;
-use crate::config::TtsMode;
+use crate::audio_player::{decode_pcm_bytes, AudioPlayer};
 use crate::speech_queue::SpeechJob;
 use crate::subtitle_client::SubtitleClient;
-use crate::tts_client::TtsClient;
-use anyhow::Result;
+use crate::tts_backend::{SynthResult, TtsBackend};
+use anyhow::{Context, Result};
+use futures_util::StreamExt;
 use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::Instant;
+use tokio::sync::Notify;
 use tracing::warn;
 
 pub struct PipelineOutcome {
@@ -16,15 +18,76 @@ pub struct PipelineOutcome {
     pub subtitle_visible_chars: u64,
     pub subtitle_wrapped: String,
     pub pipeline_latency_ms: u64,
+    pub playback_duration_ms: u64,
     pub audio_path: PathBuf,
+    /// False when streaming synthesis was cut short by a mid-stream chunk
+    /// failure or by a `stop_all` barge-in; `audio_path` and the timing fields still describe whatever
+    /// played before the cutoff.
+    pub completed: bool,
+    /// True when a P0 barge-in cut this job off via `run_pipeline`'s
+    /// `preempt` signal. When set, every other field is a zero/empty
+    /// placeholder rather than a real (partial) measurement, since the
+    /// whole pipeline was abandoned, not just audio playback.
+    pub preempted: bool,
 }
 
+struct AudioOutcome {
+    ttft_ms: u64,
+    tts_total_ms: u64,
+    pipeline_latency_ms: u64,
+    playback_duration_ms: u64,
+    audio_path: PathBuf,
+    completed: bool,
+}
+
+/// Runs the pipeline for `job`, racing it against `preempt`: a P0 barge-in
+/// (see `StateActor::enqueue_speech`) notifies `preempt`, which drops
+/// whatever the inner pipeline was doing mid-flight (cancelling the
+/// outstanding TTS request same as dropping any other future) and stops
+/// current playback immediately via `AudioPlayer::stop_all`.
 pub async fn run_pipeline(
     job: &SpeechJob,
     subtitle: &SubtitleClient,
-    tts: &TtsClient,
+    tts: &dyn TtsBackend,
+    audio: &AudioPlayer,
+    prefetched: Option<SynthResult>,
+    preempt: Arc<Notify>,
+) -> Result<PipelineOutcome> {
+    // A barge-in (P0 preempt above, or an explicit `StopAll` from
+    // `StateActor`) raised against the *previous* job can leave
+    // `cancel_flag` stuck true if that job never reached the streaming
+    // loop that's the only place consuming it (see `AudioPlayer::take_cancelled`).
+    // Clear it here, at the start of every job, so only a barge-in that
+    // lands during *this* job's own execution cancels it.
+    audio.take_cancelled();
+
+    tokio::select! {
+        _ = preempt.notified() => {
+            audio.stop_all();
+            Ok(PipelineOutcome {
+                ttft_ms: 0,
+                tts_total_ms: 0,
+                subtitle_show_s: 0.0,
+                subtitle_request_id: String::new(),
+                subtitle_visible_chars: 0,
+                subtitle_wrapped: String::new(),
+                pipeline_latency_ms: 0,
+                playback_duration_ms: 0,
+                audio_path: PathBuf::new(),
+                completed: false,
+                preempted: true,
+            })
+        }
+        outcome = run_pipeline_inner(job, subtitle, tts, audio, prefetched) => outcome,
+    }
+}
+
+async fn run_pipeline_inner(
+    job: &SpeechJob,
+    subtitle: &SubtitleClient,
+    tts: &dyn TtsBackend,
     audio: &AudioPlayer,
-    tts_mode: TtsMode,
+    prefetched: Option<SynthResult>,
 ) -> Result<PipelineOutcome> {
     let started = Instant::now();
 
@@ -33,12 +96,11 @@ pub async fn run_pipeline(
         warn!(error = %err, "subtitle post failed");
     }
 
-    let tts_started = Instant::now();
-    let synth = tts.synthesize(&job.text, tts_mode).await?;
-    let measured_ttft_ms = tts_started.elapsed().as_millis() as u64;
-
-    let audio_path = audio.play_or_fallback(&synth.wav_bytes)?;
-    let pipeline_latency_ms = started.elapsed().as_millis() as u64;
+    let audio_outcome = if job.streaming {
+        run_streaming(job, tts, audio, started).await?
+    } else {
+        run_single_shot(job, tts, audio, started, prefetched).await?
+    };
 
     let (subtitle_show_s, subtitle_req_id, subtitle_wrapped, subtitle_chars) = match subtitle_res {
         Ok(body) => (body.show_s, body.request_id, body.wrapped, body.visible_chars),
@@ -46,13 +108,227 @@ pub async fn run_pipeline(
     };
 
     Ok(PipelineOutcome {
-        ttft_ms: synth.ttft_ms.unwrap_or(measured_ttft_ms),
-        tts_total_ms: synth.total_ms.unwrap_or(pipeline_latency_ms),
+        ttft_ms: audio_outcome.ttft_ms,
+        tts_total_ms: audio_outcome.tts_total_ms,
         subtitle_show_s,
         subtitle_request_id: subtitle_req_id,
         subtitle_visible_chars: subtitle_chars,
         subtitle_wrapped,
+        pipeline_latency_ms: audio_outcome.pipeline_latency_ms,
+        playback_duration_ms: audio_outcome.playback_duration_ms,
+        audio_path: audio_outcome.audio_path,
+        completed: audio_outcome.completed,
+        preempted: false,
+    })
+}
+
+async fn run_single_shot(
+    job: &SpeechJob,
+    tts: &dyn TtsBackend,
+    audio: &AudioPlayer,
+    started: Instant,
+    prefetched: Option<SynthResult>,
+) -> Result<AudioOutcome> {
+    let tts_started = Instant::now();
+    let synth = match prefetched {
+        // Already synthesized speculatively while the previous job played;
+        // see `StateActor::spawn_prefetch_for_next`. `measured_ttft_ms`
+        // collapses to ~0 here, since this job waits on no synthesis; a
+        // backend-reported `synth.ttft_ms` still takes precedence below.
+        Some(synth) => synth,
+        None if tts.features().is_streaming => {
+            return run_single_shot_streaming(job, tts, audio, started).await;
+        }
+        None => tts.synthesize(&job.text).await?,
+    };
+    let measured_ttft_ms = tts_started.elapsed().as_millis() as u64;
+
+    let (audio_path, utterance_id) = audio.play_or_fallback(&synth.wav_bytes)?;
+    let pipeline_latency_ms = started.elapsed().as_millis() as u64;
+
+    let playback_started = Instant::now();
+    audio.await_playback_done(utterance_id).await;
+    let playback_duration_ms = playback_started.elapsed().as_millis() as u64;
+
+    Ok(AudioOutcome {
+        ttft_ms: synth.ttft_ms.unwrap_or(measured_ttft_ms),
+        tts_total_ms: synth.total_ms.unwrap_or(pipeline_latency_ms),
         pipeline_latency_ms,
+        playback_duration_ms,
         audio_path,
+        completed: true,
     })
 }
+
+/// Feeds `tts.synthesize_streaming`'s chunks straight into `AudioPlayer` as
+/// they arrive, so playback (and the recorded `ttft_ms`) starts on the
+/// first chunk instead of waiting for the whole clip to download and decode.
+/// Streamed utterances have no single WAV file to report, so `audio_path`
+/// is left empty.
+async fn run_single_shot_streaming(
+    job: &SpeechJob,
+    tts: &dyn TtsBackend,
+    audio: &AudioPlayer,
+    started: Instant,
+) -> Result<AudioOutcome> {
+    let (handle, mut chunks) = tts.synthesize_streaming(&job.text).await?;
+
+    let mut ttft_ms = None;
+    let mut wav_spec: Option<(u16, u32, u16)> = None;
+    let mut utterance_id = None;
+
+    while let Some(chunk) = chunks.next().await {
+        let chunk = chunk?;
+        if ttft_ms.is_none() {
+            ttft_ms = Some(handle.ttft_ms());
+        }
+
+        let Some((channels, sample_rate, bits_per_sample)) = wav_spec else {
+            if chunk.len() < 44 {
+                warn!("streamed tts chunk shorter than a wav header, dropping");
+                continue;
+            }
+            let reader = hound::WavReader::new(std::io::Cursor::new(&chunk[..44]))
+                .context("parse streamed wav header")?;
+            let spec = reader.spec();
+            wav_spec = Some((spec.channels, spec.sample_rate, spec.bits_per_sample));
+
+            let id = audio.begin_streaming();
+            let pcm = decode_pcm_bytes(&chunk[44..], spec.bits_per_sample);
+            audio.feed_streaming_chunk(&id, &pcm, spec.channels, spec.sample_rate);
+            utterance_id = Some(id);
+            continue;
+        };
+
+        let pcm = decode_pcm_bytes(&chunk, bits_per_sample);
+        if let Some(id) = &utterance_id {
+            audio.feed_streaming_chunk(id, &pcm, channels, sample_rate);
+        }
+    }
+
+    let pipeline_latency_ms = started.elapsed().as_millis() as u64;
+
+    let playback_started = Instant::now();
+    if let Some(utterance_id) = utterance_id {
+        audio.await_playback_done(utterance_id).await;
+    }
+    let playback_duration_ms = playback_started.elapsed().as_millis() as u64;
+
+    Ok(AudioOutcome {
+        ttft_ms: ttft_ms.unwrap_or(pipeline_latency_ms),
+        tts_total_ms: pipeline_latency_ms,
+        pipeline_latency_ms,
+        playback_duration_ms,
+        audio_path: PathBuf::new(),
+        completed: true,
+    })
+}
+
+/// Synthesizes `job.text` sentence-by-sentence, keeping at most one chunk's
+/// synthesis prefetched ahead of the chunk currently being played (depth-2
+/// pipelining) so playback can start on the first chunk instead of the
+/// whole line.
+async fn run_streaming(
+    job: &SpeechJob,
+    tts: &dyn TtsBackend,
+    audio: &AudioPlayer,
+    started: Instant,
+) -> Result<AudioOutcome> {
+    let chunks = split_into_chunks(&job.text);
+    if chunks.is_empty() {
+        return Ok(AudioOutcome {
+            ttft_ms: 0,
+            tts_total_ms: 0,
+            pipeline_latency_ms: started.elapsed().as_millis() as u64,
+            playback_duration_ms: 0,
+            audio_path: PathBuf::new(),
+            completed: true,
+        });
+    }
+
+    let mut ttft_ms = None;
+    let mut last_audio_path = PathBuf::new();
+    let mut last_utterance_id = None;
+    let mut tts_total_ms = 0_u64;
+    let mut completed = false;
+
+    let mut idx = 0_usize;
+    let mut pending = Some(tts.synthesize(&chunks[idx]));
+
+    loop {
+        if audio.take_cancelled() {
+            break;
+        }
+        let Some(fut) = pending.take() else {
+            completed = true;
+            break;
+        };
+        let synth = match fut.await {
+            Ok(synth) => synth,
+            Err(err) => {
+                warn!(error = %err, "streaming chunk synthesis failed, stopping cleanly");
+                break;
+            }
+        };
+
+        if ttft_ms.is_none() {
+            ttft_ms = Some(started.elapsed().as_millis() as u64);
+        }
+        tts_total_ms = tts_total_ms.saturating_add(synth.total_ms.unwrap_or(0));
+
+        // Create the next chunk's synthesis future now; it is polled at the
+        // top of the next iteration, while this chunk plays.
+        idx += 1;
+        if idx < chunks.len() {
+            pending = Some(tts.synthesize(&chunks[idx]));
+        }
+
+        let (audio_path, utterance_id) = audio.play_or_fallback(&synth.wav_bytes)?;
+        last_audio_path = audio_path;
+        // Wait for the chunk before this one to finish, so synthesis stays
+        // at most one chunk ahead of what is playing.
+        if let Some(prev_utterance_id) = last_utterance_id.replace(utterance_id) {
+            audio.await_playback_done(prev_utterance_id).await;
+        }
+    }
+
+    let pipeline_latency_ms = started.elapsed().as_millis() as u64;
+
+    let playback_started = Instant::now();
+    if let Some(utterance_id) = last_utterance_id {
+        audio.await_playback_done(utterance_id).await;
+    }
+    let playback_duration_ms = playback_started.elapsed().as_millis() as u64;
+
+    Ok(AudioOutcome {
+        ttft_ms: ttft_ms.unwrap_or(pipeline_latency_ms),
+        tts_total_ms,
+        pipeline_latency_ms,
+        playback_duration_ms,
+        audio_path: last_audio_path,
+        completed,
+    })
+}
+
+/// Splits on sentence/clause boundaries (`. ! ? 、 。 ！ ？`), keeping the
+/// delimiter attached so each chunk still reads naturally on its own.
+fn split_into_chunks(text: &str) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+
+    for ch in text.chars() {
+        current.push(ch);
+        if matches!(ch, '.' | '!' | '?' | '、' | '。' | '！' | '？') {
+            chunks.push(std::mem::take(&mut current));
+        }
+    }
+    if !current.trim().is_empty() {
+        chunks.push(current);
+    }
+
+    chunks
+        .into_iter()
+        .map(|c| c.trim().to_string())
+        .filter(|c| !c.is_empty())
+        .collect()
+}
@@ -43,6 +43,9 @@ pub struct SpeechJob {
     pub enqueued_ms: u64,
     pub deadline_ms: u64,
     pub dedupe_key: String,
+    /// When set, `run_pipeline` synthesizes sentence-by-sentence and starts
+    /// playback on the first chunk instead of waiting for the whole line.
+    pub streaming: bool,
 }
 
 #[derive(Clone, Debug)]
@@ -94,6 +97,18 @@ impl SpeechQueue {
         out
     }
 
+    /// Current `(p0, p1, p2)` queue depths, for the `/metrics` gauges.
+    pub fn depths(&self) -> (usize, usize, usize) {
+        (self.p0.len(), self.p1.len(), self.p2.len())
+    }
+
+    /// The job `pop_next` would return right now, without removing it.
+    /// Assumes `drop_expired` has already run this tick, so (unlike
+    /// `pop_next`) this doesn't itself skip past expired entries.
+    pub fn peek_next(&self) -> Option<&SpeechJob> {
+        self.p0.front().or_else(|| self.p1.front()).or_else(|| self.p2.front())
+    }
+
     pub fn pop_next(&mut self, now_ms: u64) -> Option<SpeechJob> {
         if let Some(job) = Self::pop_next_from_queue(&mut self.p0, now_ms, true) {
             return Some(job);
@@ -159,6 +174,7 @@ mod tests {
             enqueued_ms: 0,
             deadline_ms,
             dedupe_key: id.to_string(),
+            streaming: false,
         }
     }
 
@@ -174,6 +190,31 @@ mod tests {
         assert_eq!(q.pop_next(0).unwrap().job_id, "p2");
     }
 
+    #[test]
+    fn peek_next_matches_pop_next_without_removing() {
+        let mut q = SpeechQueue::new(8, 8, 8);
+        q.push(job("p2", SpeechPriority::P2Commentary, 10));
+        q.push(job("p1", SpeechPriority::P1ChatReply, 10));
+
+        assert_eq!(q.peek_next().unwrap().job_id, "p1");
+        assert_eq!(q.peek_next().unwrap().job_id, "p1");
+        assert_eq!(q.pop_next(0).unwrap().job_id, "p1");
+        assert_eq!(q.peek_next().unwrap().job_id, "p2");
+    }
+
+    #[test]
+    fn depths_reflect_push_and_pop() {
+        let mut q = SpeechQueue::new(8, 8, 8);
+        assert_eq!(q.depths(), (0, 0, 0));
+
+        q.push(job("p0", SpeechPriority::P0Safety, 10));
+        q.push(job("p2", SpeechPriority::P2Commentary, 10));
+        assert_eq!(q.depths(), (1, 0, 1));
+
+        q.pop_next(0);
+        assert_eq!(q.depths(), (0, 0, 1));
+    }
+
     #[test]
     fn drops_expired_non_p0() {
         let mut q = SpeechQueue::new(8, 8, 8);
@@ -3,29 +3,82 @@ use crate::action_ledger::{ActionLedger, TimeoutKind};
 use crate::audio_player::AudioPlayer;
 use crate::bridge_client::{BridgeClient, BridgeEvent};
 use crate::config::OrchestratorConfig;
-use crate::pipeline::run_pipeline;
+use crate::metrics::MetricsRegistry;
+use crate::pipeline::{run_pipeline, PipelineOutcome};
 use crate::speech_queue::{SpeechJob, SpeechPriority, SpeechQueue, SpeechSource};
 use crate::subtitle_client::SubtitleClient;
-use crate::tts_client::TtsClient;
+use crate::tts_backend::{SynthResult, TtsBackend};
 use anyhow::{Context, Result};
 use std::fs::OpenOptions;
 use std::io::Write;
+use std::sync::Arc;
 use std::time::Instant;
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
 use tracing::{info, warn};
 use uuid::Uuid;
 
+/// A speculative `TtsBackend::synthesize` kicked off for the queue's next
+/// job while the current one is still playing, so its TTFT is paid ahead of
+/// time instead of when it's actually popped. See
+/// `StateActor::spawn_prefetch_for_next`.
+struct Prefetch {
+    job_id: String,
+    deadline_ms: u64,
+    handle: JoinHandle<Result<SynthResult>>,
+}
+
+/// The speech job currently being synthesized/played by a background
+/// `run_pipeline` task. `preempt` is the handle `enqueue_speech` signals to
+/// barge in with a P0 line; `started_ms` and `queue_wait_ms`/`silence_gap_ms`
+/// are captured at pop time since they describe the moment the job left the
+/// queue, not whenever the background task happens to finish.
+struct ActivePipeline {
+    job_id: String,
+    text: String,
+    priority: SpeechPriority,
+    source: SpeechSource,
+    started_ms: u64,
+    queue_wait_ms: u64,
+    silence_gap_ms: u64,
+    prefetch_hit: bool,
+    preempt: Arc<Notify>,
+    handle: JoinHandle<Result<PipelineOutcome>>,
+}
+
 pub struct StateActor {
     cfg: OrchestratorConfig,
     bridge: BridgeClient,
     subtitle: SubtitleClient,
-    tts: TtsClient,
-    audio: AudioPlayer,
+    tts: Arc<dyn TtsBackend>,
+    audio: Arc<AudioPlayer>,
     queue: SpeechQueue,
     ledger: ActionLedger,
     t0: Instant,
     last_spoken_ms: u64,
     last_line: Option<String>,
     last_line_ms: u64,
+    /// Whether `bridge` is currently connected. While `false`, action
+    /// requests are suppressed and `run` only polls the reconnect timer
+    /// instead of `bridge.next_event`.
+    connected: bool,
+    /// Consecutive failed reconnect attempts since the connection last
+    /// dropped; reset to 0 once reconnected.
+    reconnect_failures: u32,
+    /// Backoff before the next reconnect attempt, doubling on each failure
+    /// up to `cfg.reconnect_max_backoff_ms`.
+    reconnect_backoff_ms: u64,
+    /// At most one in-flight speculative synthesis for the queue's
+    /// next-up job.
+    prefetch: Option<Prefetch>,
+    /// Live mirror of the same events written to `cfg.metrics_jsonl_path`,
+    /// served as Prometheus text format on `cfg.metrics_http_addr` (if
+    /// set). See `append_metric_line`.
+    metrics: Arc<MetricsRegistry>,
+    /// The job (if any) currently being synthesized/played by a background
+    /// `run_pipeline` task. `on_tick` only pops a new job once this is
+    /// `None`; `enqueue_speech` preempts it on an incoming P0 line.
+    active: Option<ActivePipeline>,
 }
 
 impl StateActor {
@@ -33,9 +86,10 @@ impl StateActor {
         cfg: OrchestratorConfig,
         bridge: BridgeClient,
         subtitle: SubtitleClient,
-        tts: TtsClient,
-        audio: AudioPlayer,
+        tts: Arc<dyn TtsBackend>,
+        audio: Arc<AudioPlayer>,
     ) -> Self {
+        let reconnect_backoff_ms = cfg.reconnect_initial_backoff_ms;
         Self {
             queue: SpeechQueue::new(cfg.queue_max_p0, cfg.queue_max_p1, cfg.queue_max_p2),
             ledger: ActionLedger::new(),
@@ -48,6 +102,12 @@ impl StateActor {
             last_spoken_ms: 0,
             last_line: None,
             last_line_ms: 0,
+            connected: true,
+            reconnect_failures: 0,
+            reconnect_backoff_ms,
+            prefetch: None,
+            metrics: Arc::new(MetricsRegistry::new()),
+            active: None,
         }
     }
 
@@ -55,11 +115,23 @@ impl StateActor {
         let mut interval = tokio::time::interval(std::time::Duration::from_millis(self.cfg.state_tick_ms));
         info!("state actor started");
 
+        if let Some(addr) = self.cfg.metrics_http_addr.clone() {
+            let metrics = Arc::clone(&self.metrics);
+            tokio::spawn(async move {
+                if let Err(err) = crate::metrics::serve(addr, metrics).await {
+                    warn!(error = %err, "metrics http server exited");
+                }
+            });
+        }
+
+        let reconnect_sleep = tokio::time::sleep(std::time::Duration::from_millis(0));
+        tokio::pin!(reconnect_sleep);
+
         loop {
             tokio::select! {
-                evt = self.bridge.next_event() => {
-                    match evt? {
-                        BridgeEvent::Telemetry(frame) => {
+                evt = self.bridge.next_event(), if self.connected => {
+                    match evt {
+                        Ok(BridgeEvent::Telemetry(frame)) => {
                             let now_ms = self.now_ms();
                             let line = self.make_telemetry_line(&frame);
                             self.enqueue_speech(
@@ -70,7 +142,7 @@ impl StateActor {
                                 now_ms,
                             )?;
                         }
-                        BridgeEvent::ActionAck(ack) => {
+                        Ok(BridgeEvent::ActionAck(ack)) => {
                             self.ledger.on_ack(&ack.request_id, ack.accepted);
                             if !ack.accepted {
                                 let now_ms = self.now_ms();
@@ -87,7 +159,7 @@ impl StateActor {
                                 )?;
                             }
                         }
-                        BridgeEvent::ActionResult(result) => {
+                        Ok(BridgeEvent::ActionResult(result)) => {
                             self.ledger.on_result(&result.request_id);
                             let status = crate::pb::bridge_v1::ActionStatus::from_i32(result.status)
                                 .unwrap_or(crate::pb::bridge_v1::ActionStatus::ActionStatusUnspecified);
@@ -106,25 +178,123 @@ impl StateActor {
                                 )?;
                             }
                         }
-                        BridgeEvent::Heartbeat(_hb) => {}
-                        BridgeEvent::Closed => {
+                        Ok(BridgeEvent::Heartbeat(_hb)) => {}
+                        Ok(BridgeEvent::Closed) => {
                             warn!("bridge connection closed");
-                            break;
+                            self.begin_disconnect(&mut reconnect_sleep)?;
+                        }
+                        Err(e) => {
+                            warn!(error = %e, "bridge event stream error");
+                            self.begin_disconnect(&mut reconnect_sleep)?;
                         }
                     }
                 }
+                () = &mut reconnect_sleep, if !self.connected => {
+                    self.try_reconnect(&mut reconnect_sleep).await?;
+                }
+                join_result = poll_active(&mut self.active), if self.active.is_some() => {
+                    self.finish_active(join_result).await?;
+                }
                 _ = interval.tick() => {
                     self.on_tick().await?;
                 }
             }
         }
+    }
+
+    /// Marks the bridge connection as down, emits the first-failure P0
+    /// safety line, and arms the reconnect timer. Action requests are
+    /// suppressed (see `on_tick`) until `try_reconnect` succeeds.
+    fn begin_disconnect(&mut self, reconnect_sleep: &mut std::pin::Pin<&mut tokio::time::Sleep>) -> Result<()> {
+        if self.connected {
+            self.connected = false;
+            self.reconnect_failures = 0;
+            self.reconnect_backoff_ms = self.cfg.reconnect_initial_backoff_ms;
+            let now_ms = self.now_ms();
+            self.enqueue_speech(
+                "connection lost, holding safe state".to_string(),
+                SpeechPriority::P0Safety,
+                SpeechSource::ActionSafety,
+                self.cfg.chat_deadline_ms,
+                now_ms,
+            )?;
+        }
+        reconnect_sleep
+            .as_mut()
+            .reset(tokio::time::Instant::now() + std::time::Duration::from_millis(jittered(self.reconnect_backoff_ms)));
+        Ok(())
+    }
+
+    /// One reconnect attempt. On success, replaces `self.bridge` and resumes
+    /// normal event polling; on failure, doubles the backoff (capped) and
+    /// rearms the reconnect timer. Either way, a `bridge_reconnect` metric
+    /// line is appended.
+    async fn try_reconnect(&mut self, reconnect_sleep: &mut std::pin::Pin<&mut tokio::time::Sleep>) -> Result<()> {
+        match BridgeClient::connect(
+            &self.cfg.bridge_url,
+            &self.cfg.agent_id,
+            &self.cfg.client_version,
+            &self.cfg.tls,
+        )
+        .await
+        {
+            Ok(bridge) => {
+                let attempts = self.reconnect_failures;
+                self.bridge = bridge;
+                self.connected = true;
+                self.reconnect_failures = 0;
+                self.reconnect_backoff_ms = self.cfg.reconnect_initial_backoff_ms;
+                info!(attempts, "bridge reconnected");
+
+                let now_ms = self.now_ms();
+                self.enqueue_speech(
+                    "connection restored, resuming normal operation".to_string(),
+                    SpeechPriority::P0Safety,
+                    SpeechSource::ActionSafety,
+                    self.cfg.chat_deadline_ms,
+                    now_ms,
+                )?;
+
+                self.append_metric_line(&serde_json::json!({
+                    "event": "bridge_reconnect",
+                    "status": "reconnected",
+                    "consecutive_failures": attempts,
+                }))?;
+            }
+            Err(e) => {
+                self.reconnect_failures += 1;
+                self.reconnect_backoff_ms =
+                    (self.reconnect_backoff_ms * 2).min(self.cfg.reconnect_max_backoff_ms);
+                warn!(
+                    error = %e,
+                    consecutive_failures = self.reconnect_failures,
+                    next_backoff_ms = self.reconnect_backoff_ms,
+                    "bridge reconnect attempt failed"
+                );
+
+                self.append_metric_line(&serde_json::json!({
+                    "event": "bridge_reconnect",
+                    "status": "retry",
+                    "consecutive_failures": self.reconnect_failures,
+                    "next_backoff_ms": self.reconnect_backoff_ms,
+                }))?;
+
+                reconnect_sleep.as_mut().reset(
+                    tokio::time::Instant::now() + std::time::Duration::from_millis(jittered(self.reconnect_backoff_ms)),
+                );
+            }
+        }
         Ok(())
     }
 
     async fn on_tick(&mut self) -> Result<()> {
         let now_ms = self.now_ms();
 
+        let (p0, p1, p2) = self.queue.depths();
+        self.metrics.set_queue_depths(p0, p1, p2);
+
         for dropped in self.queue.drop_expired(now_ms) {
+            self.invalidate_prefetch(&dropped.job.job_id);
             self.append_metric_line(&serde_json::json!({
                 "event": "speech_dropped",
                 "job_id": dropped.job.job_id,
@@ -136,10 +306,15 @@ impl StateActor {
         }
 
         for timeout in self.ledger.poll_timeouts(now_ms) {
-            let timeout_label = match timeout.kind {
-                TimeoutKind::Ack => "ack_timeout",
-                TimeoutKind::Result => "result_timeout",
+            let (timeout_label, metric_kind) = match timeout.kind {
+                TimeoutKind::Ack => ("ack_timeout", "ack"),
+                TimeoutKind::Result => ("result_timeout", "result"),
             };
+            self.append_metric_line(&serde_json::json!({
+                "event": "action_timeout",
+                "request_id": timeout.request_id,
+                "kind": metric_kind,
+            }))?;
             let line = format!(
                 "Action {} reached {}. sending StopAll.",
                 timeout.request_id, timeout_label
@@ -152,11 +327,21 @@ impl StateActor {
                 now_ms,
             )?;
 
+            // Barge in locally too: a StopAll is as much "stop talking" as it
+            // is "stop acting", and should silence TTS within milliseconds
+            // rather than waiting on a round trip to the bridge.
+            self.audio.stop_all();
+
             if !action_client::is_allowlisted(crate::pb::bridge_v1::ActionType::ActionTypeStopAll) {
                 warn!("stop_all is not allowlisted, skip emergency action send");
                 continue;
             }
 
+            if !self.connected {
+                warn!("bridge disconnected, suppressing stop_all action send until reconnected");
+                continue;
+            }
+
             let stop_req =
                 action_client::build_stop_all_request(&self.cfg.primary_game_agent_id, wall_unix_ms(), 1500);
             let request_id = stop_req.request_id.clone();
@@ -182,44 +367,176 @@ impl StateActor {
             )?;
         }
 
-        let Some(job) = self.queue.pop_next(now_ms) else {
+        if self.active.is_none() {
+            if let Some(job) = self.queue.pop_next(now_ms) {
+                let prefetched = self.take_prefetch_for(&job.job_id, now_ms).await;
+                let prefetch_hit = prefetched.is_some();
+                self.spawn_prefetch_for_next();
+
+                let queue_wait_ms = now_ms.saturating_sub(job.enqueued_ms);
+                let silence_gap_ms = now_ms.saturating_sub(self.last_spoken_ms);
+
+                let preempt = Arc::new(Notify::new());
+                let subtitle = self.subtitle.clone();
+                let tts = Arc::clone(&self.tts);
+                let audio = Arc::clone(&self.audio);
+                let job_clone = job.clone();
+                let preempt_for_task = Arc::clone(&preempt);
+                let handle = tokio::spawn(async move {
+                    run_pipeline(&job_clone, &subtitle, tts.as_ref(), audio.as_ref(), prefetched, preempt_for_task).await
+                });
+
+                self.active = Some(ActivePipeline {
+                    job_id: job.job_id,
+                    text: job.text,
+                    priority: job.priority,
+                    source: job.source,
+                    started_ms: now_ms,
+                    queue_wait_ms,
+                    silence_gap_ms,
+                    prefetch_hit,
+                    preempt,
+                    handle,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Handles a background `run_pipeline` task finishing (normally or via
+    /// preemption), clearing `self.active` and recording the same
+    /// `speech_pipeline` metric `on_tick` used to record inline before this
+    /// job moved to a background task. A preempted job also gets a
+    /// `speech_preempted` event instead of (not in addition to, since it
+    /// never got to finish) the `speech_pipeline` one.
+    async fn finish_active(
+        &mut self,
+        join_result: std::result::Result<Result<PipelineOutcome>, tokio::task::JoinError>,
+    ) -> Result<()> {
+        let Some(active) = self.active.take() else {
             return Ok(());
         };
 
-        let queue_wait_ms = now_ms.saturating_sub(job.enqueued_ms);
-        let silence_gap_ms = now_ms.saturating_sub(self.last_spoken_ms);
-        let outcome = run_pipeline(
-            &job,
-            &self.subtitle,
-            &self.tts,
-            &self.audio,
-            self.cfg.tts_mode(),
-        )
-        .await?;
+        let outcome = match join_result {
+            Ok(Ok(outcome)) => outcome,
+            Ok(Err(err)) => {
+                warn!(error = %err, job_id = %active.job_id, "speech pipeline failed");
+                return Ok(());
+            }
+            Err(join_err) => {
+                warn!(error = %join_err, job_id = %active.job_id, "speech pipeline task did not complete");
+                return Ok(());
+            }
+        };
 
-        self.last_spoken_ms = self.now_ms();
+        let now_ms = self.now_ms();
+
+        if outcome.preempted {
+            self.append_metric_line(&serde_json::json!({
+                "event": "speech_preempted",
+                "job_id": active.job_id,
+                "cut_at_ms": now_ms.saturating_sub(active.started_ms),
+            }))?;
+            return Ok(());
+        }
+
+        self.last_spoken_ms = now_ms;
 
         self.append_metric_line(&serde_json::json!({
             "event": "speech_pipeline",
-            "job_id": job.job_id,
-            "text": job.text,
-            "priority": job.priority.as_str(),
-            "source": job.source.as_str(),
+            "job_id": active.job_id,
+            "text": active.text,
+            "priority": active.priority.as_str(),
+            "source": active.source.as_str(),
             "ttft_ms": outcome.ttft_ms,
             "tts_total_ms": outcome.tts_total_ms,
             "subtitle_show_s": outcome.subtitle_show_s,
             "subtitle_request_id": outcome.subtitle_request_id,
             "subtitle_visible_chars": outcome.subtitle_visible_chars,
             "subtitle_wrapped": outcome.subtitle_wrapped,
-            "silence_gap_ms": silence_gap_ms,
-            "queue_wait_ms": queue_wait_ms,
+            "silence_gap_ms": active.silence_gap_ms,
+            "queue_wait_ms": active.queue_wait_ms,
             "pipeline_latency_ms": outcome.pipeline_latency_ms,
+            "playback_duration_ms": outcome.playback_duration_ms,
             "audio_path": outcome.audio_path.display().to_string(),
+            "prefetch_hit": active.prefetch_hit,
         }))?;
 
         Ok(())
     }
 
+    /// Drops the in-flight prefetch, if any, and aborts its synthesis task.
+    /// Called when the job it targeted is no longer what `pop_next` would
+    /// return next (dropped for expiry, or jumped by an incoming P0 line).
+    fn invalidate_prefetch(&mut self, job_id: &str) {
+        if self.prefetch.as_ref().is_some_and(|p| p.job_id == job_id) {
+            if let Some(p) = self.prefetch.take() {
+                p.handle.abort();
+            }
+        }
+    }
+
+    /// If the job just popped matches the in-flight prefetch, awaits and
+    /// returns its result (a miss becomes `None`, logged but otherwise
+    /// ignored so the caller falls back to synthesizing normally). Discards
+    /// a prefetch whose job has outlived its own `deadline_ms` rather than
+    /// handing back a stale result.
+    async fn take_prefetch_for(&mut self, job_id: &str, now_ms: u64) -> Option<SynthResult> {
+        let matches = self.prefetch.as_ref().is_some_and(|p| p.job_id == job_id);
+        if !matches {
+            return None;
+        }
+        let prefetch = self.prefetch.take()?;
+        if now_ms > prefetch.deadline_ms {
+            prefetch.handle.abort();
+            return None;
+        }
+
+        match prefetch.handle.await {
+            Ok(Ok(synth)) => Some(synth),
+            Ok(Err(err)) => {
+                warn!(error = %err, job_id, "prefetch synthesis failed, falling back to inline synth");
+                None
+            }
+            Err(join_err) => {
+                warn!(error = %join_err, job_id, "prefetch task did not complete, falling back to inline synth");
+                None
+            }
+        }
+    }
+
+    /// Peeks the queue's next-up job and, if it's eligible (not already
+    /// being prefetched, not itself a streaming synth), kicks off its
+    /// `TtsBackend::synthesize` concurrently so that by the time it's
+    /// popped its TTFT has already been paid.
+    fn spawn_prefetch_for_next(&mut self) {
+        let Some(next) = self.queue.peek_next() else {
+            return;
+        };
+        if next.streaming || self.tts.features().is_streaming {
+            return;
+        }
+        if self.prefetch.as_ref().is_some_and(|p| p.job_id == next.job_id) {
+            return;
+        }
+
+        let tts = Arc::clone(&self.tts);
+        let text = next.text.clone();
+        let job_id = next.job_id.clone();
+        let deadline_ms = next.deadline_ms;
+
+        if let Some(old) = self.prefetch.take() {
+            old.handle.abort();
+        }
+        let handle = tokio::spawn(async move { tts.synthesize(&text).await });
+        self.prefetch = Some(Prefetch {
+            job_id,
+            deadline_ms,
+            handle,
+        });
+    }
+
     fn enqueue_speech(
         &mut self,
         text: String,
@@ -248,8 +565,30 @@ impl StateActor {
             enqueued_ms: now_ms,
             deadline_ms: now_ms.saturating_add(deadline_delta_ms),
             dedupe_key,
+            streaming: matches!(source, SpeechSource::Telemetry | SpeechSource::Filler),
         };
 
+        if priority == SpeechPriority::P0Safety {
+            // A P0 line always jumps the queue, so whatever was prefetched
+            // for the old next-up job is no longer next; let on_tick decide
+            // what (if anything) to prefetch for the new front.
+            if let Some(p) = self.prefetch.take() {
+                p.handle.abort();
+            }
+
+            // Barge-in: a lower-priority clip already playing would otherwise
+            // delay this safety line by however much of it is left. Signal
+            // its pipeline task to cut playback and abort its TTS request;
+            // `finish_active` records `speech_preempted` once it unwinds, and
+            // `on_tick` pops this job on its next poll since `self.active`
+            // clears.
+            if let Some(active) = &self.active {
+                if active.priority != SpeechPriority::P0Safety {
+                    active.preempt.notify_one();
+                }
+            }
+        }
+
         if let Some(dropped) = self.queue.push(job) {
             self.append_metric_line(&serde_json::json!({
                 "event": "speech_dropped",
@@ -293,6 +632,8 @@ impl StateActor {
             .with_context(|| format!("open metrics file: {}", self.cfg.metrics_jsonl_path))?;
         writeln!(f, "{}", serde_json::to_string(value).context("serialize metrics")?)
             .context("write metrics line")?;
+
+        self.metrics.record_event(value);
         Ok(())
     }
 
@@ -305,6 +646,26 @@ fn normalize_dedupe_key(text: &str) -> String {
     text.split_whitespace().collect::<String>()
 }
 
+/// Awaits the in-flight pipeline task's `JoinHandle`. Only called from
+/// `run`'s `select!` guarded by `self.active.is_some()`, so the `expect`
+/// here never fires.
+async fn poll_active(
+    active: &mut Option<ActivePipeline>,
+) -> std::result::Result<Result<PipelineOutcome>, tokio::task::JoinError> {
+    (&mut active.as_mut().expect("checked by select guard").handle).await
+}
+
+/// Adds up to +/-10% jitter to a reconnect backoff so multiple orchestrators
+/// reconnecting to the same bridge don't all retry in lockstep.
+fn jittered(base_ms: u64) -> u64 {
+    let spread = (base_ms / 5).max(1);
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .subsec_nanos() as u64;
+    base_ms - (spread / 2) + (nanos % spread)
+}
+
 fn wall_unix_ms() -> u64 {
     use std::time::{SystemTime, UNIX_EPOCH};
     SystemTime::now()
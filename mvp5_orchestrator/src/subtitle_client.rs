@@ -1,10 +1,39 @@
-use anyhow::{Context, Result};
 use serde::Deserialize;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+/// Knobs for `SubtitleClient`'s retry behavior. See `RelayConfig` in
+/// `mvp1_bridge_server` for the analogous "bag of tunables" pattern.
+#[derive(Clone, Debug)]
+pub struct SubtitleRetryConfig {
+    /// Timeout applied to each individual attempt, not the call as a whole.
+    pub request_timeout_ms: u64,
+    /// Attempts are capped by whichever of this or `retry_deadline_ms` is
+    /// hit first.
+    pub max_attempts: u32,
+    pub initial_backoff_ms: u64,
+    pub max_backoff_ms: u64,
+    /// Wall-clock budget for the whole call, across all attempts.
+    pub retry_deadline_ms: u64,
+}
+
+impl Default for SubtitleRetryConfig {
+    fn default() -> Self {
+        Self {
+            request_timeout_ms: 2_000,
+            max_attempts: 3,
+            initial_backoff_ms: 200,
+            max_backoff_ms: 2_000,
+            retry_deadline_ms: 6_000,
+        }
+    }
+}
 
 #[derive(Clone)]
 pub struct SubtitleClient {
     http: reqwest::Client,
     base_url: String,
+    retry: SubtitleRetryConfig,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -16,32 +45,155 @@ pub struct SubtitleResponse {
     pub show_s: f64,
 }
 
+/// Why a `post_subtitle` attempt (or the whole retried call) failed, in
+/// place of collapsing every failure mode into one `anyhow::Error` string.
+#[derive(Debug)]
+pub enum SubtitleError {
+    /// The per-attempt `request_timeout_ms` elapsed before a response came
+    /// back.
+    Timeout,
+    /// The request never reached a response at all (connect failure, reset,
+    /// etc.), as opposed to a non-success status.
+    Transport(String),
+    Status(reqwest::StatusCode),
+    NotOk,
+    Decode(String),
+}
+
+impl std::fmt::Display for SubtitleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubtitleError::Timeout => write!(f, "subtitle request timed out"),
+            SubtitleError::Transport(msg) => write!(f, "subtitle request failed: {msg}"),
+            SubtitleError::Status(status) => {
+                write!(f, "subtitle request returned non-success status: {status}")
+            }
+            SubtitleError::NotOk => write!(f, "subtitle gateway returned ok=false"),
+            SubtitleError::Decode(msg) => write!(f, "subtitle decode failed: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for SubtitleError {}
+
+impl SubtitleError {
+    /// Transient failures (timeouts, connect errors, 5xx/429 statuses) are
+    /// worth retrying with the same `request_id`; a gateway that's already
+    /// told us `ok=false` or handed back an undecodable body isn't going to
+    /// change its answer on the same input.
+    fn is_retryable(&self) -> bool {
+        match self {
+            SubtitleError::Timeout | SubtitleError::Transport(_) => true,
+            SubtitleError::Status(status) => {
+                status.is_server_error() || *status == reqwest::StatusCode::TOO_MANY_REQUESTS
+            }
+            SubtitleError::NotOk | SubtitleError::Decode(_) => false,
+        }
+    }
+}
+
 impl SubtitleClient {
     pub fn new(base_url: String) -> Self {
-        Self {
-            http: reqwest::Client::new(),
-            base_url,
-        }
+        Self::with_retry_config(base_url, SubtitleRetryConfig::default())
+    }
+
+    pub fn with_retry_config(base_url: String, retry: SubtitleRetryConfig) -> Self {
+        let http = reqwest::Client::builder()
+            // Keeps the connection to the subtitle gateway warm across
+            // retries and across successive calls, instead of
+            // reconnecting+re-handshaking TLS on every post.
+            .tcp_keepalive(Duration::from_secs(60))
+            .pool_idle_timeout(Duration::from_secs(90))
+            .build()
+            .expect("build subtitle http client");
+        Self { http, base_url, retry }
     }
 
-    pub async fn post_subtitle(&self, text: &str) -> Result<SubtitleResponse> {
+    /// Posts `text`, retrying transient failures with the same `request_id`
+    /// so a re-send after a timeout hits the gateway's dedup cache instead
+    /// of double-displaying the line.
+    pub async fn post_subtitle(&self, text: &str) -> Result<SubtitleResponse, SubtitleError> {
+        let request_id = Uuid::new_v4().to_string();
         let url = format!("{}/v1/subtitle", self.base_url);
-        let res = self
-            .http
-            .post(url)
-            .json(&serde_json::json!({ "text": text }))
-            .send()
-            .await
-            .context("subtitle request failed")?;
+        let deadline = Instant::now() + Duration::from_millis(self.retry.retry_deadline_ms);
+        let mut backoff_ms = self.retry.initial_backoff_ms;
+
+        let mut attempt = 0_u32;
+        loop {
+            attempt += 1;
+            let err = match self.try_once(&url, text, &request_id, deadline).await {
+                Ok(body) => return Ok(body),
+                Err(err) => err,
+            };
+
+            if attempt >= self.retry.max_attempts || !err.is_retryable() || Instant::now() >= deadline {
+                return Err(err);
+            }
+
+            let sleep_ms = jittered(backoff_ms).min(self.retry.max_backoff_ms);
+            if Duration::from_millis(sleep_ms) >= deadline.saturating_duration_since(Instant::now()) {
+                return Err(err);
+            }
+            tokio::time::sleep(Duration::from_millis(sleep_ms)).await;
+            backoff_ms = (backoff_ms * 2).min(self.retry.max_backoff_ms);
+        }
+    }
+
+    /// Posts `texts` one after another through the shared client, so the
+    /// pooled connection is reused; each post waits for the previous one to
+    /// finish and has its own independent retry budget.
+    pub async fn post_subtitle_batch(&self, texts: &[&str]) -> Vec<Result<SubtitleResponse, SubtitleError>> {
+        let mut results = Vec::with_capacity(texts.len());
+        for text in texts {
+            results.push(self.post_subtitle(text).await);
+        }
+        results
+    }
+
+    async fn try_once(
+        &self,
+        url: &str,
+        text: &str,
+        request_id: &str,
+        deadline: Instant,
+    ) -> Result<SubtitleResponse, SubtitleError> {
+        let attempt_timeout = Duration::from_millis(self.retry.request_timeout_ms)
+            .min(deadline.saturating_duration_since(Instant::now()));
+        let send_res = tokio::time::timeout(
+            attempt_timeout,
+            self.http
+                .post(url)
+                .json(&serde_json::json!({ "text": text, "request_id": request_id }))
+                .send(),
+        )
+        .await;
+
+        let res = match send_res {
+            Ok(Ok(res)) => res,
+            Ok(Err(err)) => return Err(SubtitleError::Transport(err.to_string())),
+            Err(_) => return Err(SubtitleError::Timeout),
+        };
 
         if !res.status().is_success() {
-            anyhow::bail!("subtitle request returned non-success status: {}", res.status());
+            return Err(SubtitleError::Status(res.status()));
         }
 
-        let body: SubtitleResponse = res.json().await.context("subtitle decode failed")?;
+        let body: SubtitleResponse = res.json().await.map_err(|err| SubtitleError::Decode(err.to_string()))?;
         if !body.ok {
-            anyhow::bail!("subtitle gateway returned ok=false");
+            return Err(SubtitleError::NotOk);
         }
         Ok(body)
     }
 }
+
+/// Adds up to +/-10% jitter to a retry backoff so multiple speech jobs
+/// retrying at once don't all hit the gateway in lockstep. Mirrors
+/// `state_actor::jittered`.
+fn jittered(base_ms: u64) -> u64 {
+    let spread = (base_ms / 5).max(1);
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .subsec_nanos() as u64;
+    base_ms - (spread / 2) + (nanos % spread)
+}
@@ -0,0 +1,191 @@
+use crate::config::TtsBackendKind;
+use crate::tts_client::TtsClient;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use bytes::Bytes;
+use futures_util::stream::{self, Stream};
+use std::pin::Pin;
+use std::process::Command;
+use std::time::Instant;
+use uuid::Uuid;
+
+/// Identifies one speak/synthesize request so callers can correlate it with
+/// lifecycle callbacks fired later by the audio layer.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct UtteranceId(pub String);
+
+impl UtteranceId {
+    pub fn new() -> Self {
+        Self(Uuid::new_v4().to_string())
+    }
+}
+
+/// What a given backend can actually do, so callers can adapt UI/config
+/// instead of discovering unsupported-parameter errors at call time.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Features {
+    pub supports_rate: bool,
+    pub supports_pitch: bool,
+    pub supports_volume: bool,
+    pub is_streaming: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct SynthResult {
+    pub wav_bytes: Vec<u8>,
+    pub ttft_ms: Option<u64>,
+    pub total_ms: Option<u64>,
+}
+
+/// Handle to an in-flight streaming synthesis. The caller should call
+/// `ttft_ms` exactly once, right when it receives the first chunk off the
+/// paired stream, so it records a real time-to-first-chunk instead of the
+/// full-clip latency.
+pub struct SynthHandle {
+    started: Instant,
+}
+
+impl SynthHandle {
+    pub fn new() -> Self {
+        Self { started: Instant::now() }
+    }
+
+    pub fn ttft_ms(&self) -> u64 {
+        self.started.elapsed().as_millis() as u64
+    }
+}
+
+impl Default for SynthHandle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A TTS engine. Backends that only produce bytes (the remote HTTP service)
+/// implement `synthesize`; backends that own playback themselves (native OS
+/// synthesizers) implement `speak` and can leave `synthesize` unsupported.
+#[async_trait]
+pub trait TtsBackend: Send + Sync {
+    fn features(&self) -> Features;
+
+    /// Render `text` to WAV bytes for the caller to play itself.
+    async fn synthesize(&self, text: &str) -> Result<SynthResult>;
+
+    /// Synthesize `text`, yielding WAV bytes as they're produced instead of
+    /// buffering the whole clip. Default implementation falls back to
+    /// `synthesize` and yields the full result as a single chunk; backends
+    /// with a real streaming transport (see `TtsClient`) should override it.
+    async fn synthesize_streaming(
+        &self,
+        text: &str,
+    ) -> Result<(SynthHandle, Pin<Box<dyn Stream<Item = Result<Bytes>> + Send>>)> {
+        let handle = SynthHandle::new();
+        let synth = self.synthesize(text).await?;
+        let chunk = stream::once(async move { Ok(Bytes::from(synth.wav_bytes)) });
+        Ok((handle, Box::pin(chunk)))
+    }
+
+    /// Speak `text` directly, returning once the utterance has been queued
+    /// with the underlying engine. Default implementation synthesizes and
+    /// reports an id without playing anything; backends that own audio
+    /// output should override this.
+    async fn speak(&self, text: &str) -> Result<UtteranceId> {
+        self.synthesize(text).await?;
+        Ok(UtteranceId::new())
+    }
+}
+
+/// macOS `say` command. No WAV bytes are produced; `say` owns playback.
+pub struct MacSayBackend;
+
+#[async_trait]
+impl TtsBackend for MacSayBackend {
+    fn features(&self) -> Features {
+        Features {
+            supports_rate: true,
+            supports_pitch: false,
+            supports_volume: false,
+            is_streaming: false,
+        }
+    }
+
+    async fn synthesize(&self, _text: &str) -> Result<SynthResult> {
+        anyhow::bail!("MacSayBackend does not produce wav bytes; use speak()")
+    }
+
+    async fn speak(&self, text: &str) -> Result<UtteranceId> {
+        Command::new("say").arg(text).spawn().context("spawn say")?;
+        Ok(UtteranceId::new())
+    }
+}
+
+/// Linux `speech-dispatcher` via its `spd-say` CLI.
+pub struct SpeechDispatcherBackend;
+
+#[async_trait]
+impl TtsBackend for SpeechDispatcherBackend {
+    fn features(&self) -> Features {
+        Features {
+            supports_rate: true,
+            supports_pitch: true,
+            supports_volume: true,
+            is_streaming: false,
+        }
+    }
+
+    async fn synthesize(&self, _text: &str) -> Result<SynthResult> {
+        anyhow::bail!("SpeechDispatcherBackend does not produce wav bytes; use speak()")
+    }
+
+    async fn speak(&self, text: &str) -> Result<UtteranceId> {
+        Command::new("spd-say")
+            .arg(text)
+            .spawn()
+            .context("spawn spd-say")?;
+        Ok(UtteranceId::new())
+    }
+}
+
+/// Windows WinRT `Windows.Media.SpeechSynthesis.SpeechSynthesizer`, invoked
+/// through powershell since the orchestrator has no direct WinRT bindings.
+pub struct WindowsSpeechBackend;
+
+#[async_trait]
+impl TtsBackend for WindowsSpeechBackend {
+    fn features(&self) -> Features {
+        Features {
+            supports_rate: true,
+            supports_pitch: false,
+            supports_volume: true,
+            is_streaming: false,
+        }
+    }
+
+    async fn synthesize(&self, _text: &str) -> Result<SynthResult> {
+        anyhow::bail!("WindowsSpeechBackend does not produce wav bytes; use speak()")
+    }
+
+    async fn speak(&self, text: &str) -> Result<UtteranceId> {
+        let escaped = text.replace('\'', "''");
+        let cmd = format!(
+            "Add-Type -AssemblyName System.Speech; \
+             (New-Object System.Speech.Synthesis.SpeechSynthesizer).Speak('{escaped}')"
+        );
+        Command::new("powershell")
+            .args(["-NoProfile", "-Command", &cmd])
+            .spawn()
+            .context("spawn windows speech synthesizer")?;
+        Ok(UtteranceId::new())
+    }
+}
+
+/// Builds the configured `TtsBackend`. `remote` is only used for
+/// `TtsBackendKind::Remote`; for the native kinds it is dropped unused.
+pub fn build_backend(kind: TtsBackendKind, remote: TtsClient) -> Box<dyn TtsBackend> {
+    match kind {
+        TtsBackendKind::Remote => Box::new(remote),
+        TtsBackendKind::WindowsSpeech => Box::new(WindowsSpeechBackend),
+        TtsBackendKind::SpeechDispatcher => Box::new(SpeechDispatcherBackend),
+        TtsBackendKind::MacSay => Box::new(MacSayBackend),
+    }
+}
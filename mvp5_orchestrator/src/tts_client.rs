@@ -1,19 +1,23 @@
 use crate::config::TtsMode;
+use crate::tts_backend::{Features, SynthHandle, SynthResult, TtsBackend, UtteranceId};
 use anyhow::{Context, Result};
+use async_trait::async_trait;
 use base64::Engine;
+use bytes::{Bytes, BytesMut};
+use futures_util::stream::{self, Stream, StreamExt};
 use serde::Deserialize;
+use std::pin::Pin;
 
+/// Canonical PCM WAV header size, in bytes (`RIFF`/`fmt `/`data` chunk
+/// headers with no extra chunks in between).
+const WAV_HEADER_BYTES: usize = 44;
+
+/// TTS backend talking to the remote MiqBOT TTS microservice over HTTP.
 #[derive(Clone)]
 pub struct TtsClient {
     http: reqwest::Client,
     base_url: String,
-}
-
-#[derive(Debug, Clone)]
-pub struct SynthResult {
-    pub wav_bytes: Vec<u8>,
-    pub ttft_ms: Option<u64>,
-    pub total_ms: Option<u64>,
+    mode: TtsMode,
 }
 
 #[derive(Debug, Deserialize)]
@@ -24,17 +28,19 @@ struct TtsWithMetaResponse {
 }
 
 impl TtsClient {
-    pub fn new(base_url: String) -> Self {
+    pub fn new(base_url: String, mode: TtsMode) -> Self {
         Self {
             http: reqwest::Client::new(),
             base_url,
+            mode,
         }
     }
 
-    pub async fn synthesize(&self, text: &str, mode: TtsMode) -> Result<SynthResult> {
+    pub async fn synthesize_with_mode(&self, text: &str, mode: TtsMode) -> Result<SynthResult> {
         match mode {
             TtsMode::WavOnly => self.synthesize_wav_only(text).await,
             TtsMode::WithMeta => self.synthesize_with_meta(text).await,
+            TtsMode::Streaming => self.synthesize_streaming_buffered(text).await,
         }
     }
 
@@ -93,4 +99,126 @@ impl TtsClient {
             total_ms: Some(body.total_ms),
         })
     }
+
+    /// Posts to `/v1/tts_stream` and returns chunks as the service produces
+    /// them, instead of waiting for the whole clip like `synthesize_wav_only`.
+    /// The WAV header plus first PCM frame are buffered into a single first
+    /// chunk (so a caller decoding with `hound` always has a complete header
+    /// to work with); every chunk after that is passed through untouched.
+    pub async fn synthesize_stream_chunks(
+        &self,
+        text: &str,
+    ) -> Result<(SynthHandle, Pin<Box<dyn Stream<Item = Result<Bytes>> + Send>>)> {
+        let url = format!("{}/v1/tts_stream", self.base_url);
+        let handle = SynthHandle::new();
+        let res = self
+            .http
+            .post(url)
+            .json(&serde_json::json!({
+                "text": text,
+                "sample_rate_hz": 48000,
+            }))
+            .send()
+            .await
+            .context("tts_stream request failed")?;
+
+        if !res.status().is_success() {
+            anyhow::bail!("tts_stream request returned non-success status: {}", res.status());
+        }
+
+        let body = res.bytes_stream().map(|r| r.context("read tts_stream chunk"));
+        let stream = buffer_wav_header(body).await?;
+        Ok((handle, stream))
+    }
+
+    /// Fallback for callers that want one buffered `SynthResult` rather than
+    /// the raw chunk stream, e.g. the sentence-chunked path in
+    /// `pipeline::run_streaming`, which calls `TtsBackend::synthesize`
+    /// regardless of the configured `TtsMode`.
+    async fn synthesize_streaming_buffered(&self, text: &str) -> Result<SynthResult> {
+        let (handle, mut chunks) = self.synthesize_stream_chunks(text).await?;
+
+        let mut wav_bytes = Vec::new();
+        let mut ttft_ms = None;
+        while let Some(chunk) = chunks.next().await {
+            let chunk = chunk?;
+            if ttft_ms.is_none() {
+                ttft_ms = Some(handle.ttft_ms());
+            }
+            wav_bytes.extend_from_slice(&chunk);
+        }
+
+        Ok(SynthResult {
+            wav_bytes,
+            ttft_ms,
+            total_ms: None,
+        })
+    }
+}
+
+/// Consumes just enough of a raw WAV byte stream to cover the canonical
+/// 44-byte header plus one PCM frame, then re-emits that prefix as a single
+/// chunk followed by the remainder of the stream untouched.
+async fn buffer_wav_header(
+    mut chunks: impl Stream<Item = Result<Bytes>> + Send + Unpin + 'static,
+) -> Result<Pin<Box<dyn Stream<Item = Result<Bytes>> + Send>>> {
+    let mut buf = BytesMut::new();
+    let mut target = WAV_HEADER_BYTES;
+
+    loop {
+        if buf.len() >= target {
+            break;
+        }
+        let Some(chunk) = chunks.next().await else {
+            // Stream ended before a full header arrived; hand back whatever
+            // we have so the caller's header decode surfaces a clear error.
+            break;
+        };
+        buf.extend_from_slice(&chunk?);
+        if target == WAV_HEADER_BYTES && buf.len() >= WAV_HEADER_BYTES {
+            target = WAV_HEADER_BYTES + frame_bytes(&buf[..WAV_HEADER_BYTES]);
+        }
+    }
+
+    let head = buf.freeze();
+    let rest = stream::once(async move { Ok(head) }).chain(chunks);
+    Ok(Box::pin(rest))
+}
+
+/// Bytes in one PCM frame across all channels, read from the canonical WAV
+/// header's `NumChannels`/`BitsPerSample` fields (offsets 22 and 34).
+fn frame_bytes(header: &[u8]) -> usize {
+    let channels = u16::from_le_bytes([header[22], header[23]]) as usize;
+    let bits_per_sample = u16::from_le_bytes([header[34], header[35]]) as usize;
+    channels * (bits_per_sample / 8).max(1)
+}
+
+#[async_trait]
+impl TtsBackend for TtsClient {
+    fn features(&self) -> Features {
+        Features {
+            supports_rate: false,
+            supports_pitch: false,
+            supports_volume: false,
+            is_streaming: matches!(self.mode, TtsMode::Streaming),
+        }
+    }
+
+    async fn synthesize(&self, text: &str) -> Result<SynthResult> {
+        self.synthesize_with_mode(text, self.mode).await
+    }
+
+    async fn synthesize_streaming(
+        &self,
+        text: &str,
+    ) -> Result<(SynthHandle, Pin<Box<dyn Stream<Item = Result<Bytes>> + Send>>)> {
+        self.synthesize_stream_chunks(text).await
+    }
+
+    async fn speak(&self, text: &str) -> Result<UtteranceId> {
+        // The remote backend only produces bytes; callers play them through
+        // AudioPlayer rather than having this backend speak directly.
+        self.synthesize(text).await?;
+        Ok(UtteranceId::new())
+    }
 }